@@ -1,3 +1,5 @@
+use crate::json_writer::JsonValue;
+use crate::keccak::keccak256;
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -5,9 +7,7 @@ pub struct AbiInput {
     pub name: Option<String>,
     pub r#type: String,
     pub indexed: Option<bool>,
-    #[allow(dead_code)]
     pub internal_type: Option<String>,
-    #[allow(dead_code)]
     pub components: Option<Vec<AbiInput>>,
 }
 
@@ -15,9 +15,7 @@ pub struct AbiInput {
 pub struct AbiOutput {
     pub name: Option<String>,
     pub r#type: String,
-    #[allow(dead_code)]
     pub internal_type: Option<String>,
-    #[allow(dead_code)]
     pub components: Option<Vec<AbiOutput>>,
 }
 
@@ -28,14 +26,170 @@ pub struct AbiItem {
     pub inputs: Option<Vec<AbiInput>>,
     pub outputs: Option<Vec<AbiOutput>>,
     pub state_mutability: Option<String>,
-    #[allow(dead_code)]
     pub anonymous: Option<bool>,
-    #[allow(dead_code)]
     pub payable: Option<bool>,
-    #[allow(dead_code)]
     pub constant: Option<bool>,
 }
 
+/// Expands `tuple` types into their parenthesised component list, keeping
+/// any array suffix: `tuple[]` with `(address,uint256)` becomes
+/// `(address,uint256)[]`.
+fn canonical_type(r#type: &str, component_types: Option<Vec<String>>) -> String {
+    match (r#type.strip_prefix("tuple"), component_types) {
+        (Some(suffix), Some(types)) => format!("({}){suffix}", types.join(",")),
+        _ => r#type.to_string(),
+    }
+}
+
+impl AbiInput {
+    pub fn canonical_type(&self) -> String {
+        canonical_type(
+            &self.r#type,
+            self.components
+                .as_ref()
+                .map(|c| c.iter().map(AbiInput::canonical_type).collect()),
+        )
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut entries = vec![
+            (
+                "name".to_string(),
+                JsonValue::string(self.name.as_deref().unwrap_or("")),
+            ),
+            ("type".to_string(), JsonValue::string(&self.r#type)),
+        ];
+        if let Some(indexed) = self.indexed {
+            entries.push(("indexed".to_string(), JsonValue::Bool(indexed)));
+        }
+        if let Some(internal_type) = &self.internal_type {
+            entries.push(("internalType".to_string(), JsonValue::string(internal_type)));
+        }
+        if let Some(components) = &self.components {
+            entries.push((
+                "components".to_string(),
+                JsonValue::Array(components.iter().map(AbiInput::to_json).collect()),
+            ));
+        }
+        JsonValue::Object(entries)
+    }
+}
+
+impl AbiOutput {
+    pub fn canonical_type(&self) -> String {
+        canonical_type(
+            &self.r#type,
+            self.components
+                .as_ref()
+                .map(|c| c.iter().map(AbiOutput::canonical_type).collect()),
+        )
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut entries = vec![
+            (
+                "name".to_string(),
+                JsonValue::string(self.name.as_deref().unwrap_or("")),
+            ),
+            ("type".to_string(), JsonValue::string(&self.r#type)),
+        ];
+        if let Some(internal_type) = &self.internal_type {
+            entries.push(("internalType".to_string(), JsonValue::string(internal_type)));
+        }
+        if let Some(components) = &self.components {
+            entries.push((
+                "components".to_string(),
+                JsonValue::Array(components.iter().map(AbiOutput::to_json).collect()),
+            ));
+        }
+        JsonValue::Object(entries)
+    }
+}
+
+impl AbiItem {
+    /// The `name(type1,type2)` form hashed for selectors and event topics.
+    /// Only functions, events and errors have one.
+    pub fn canonical_signature(&self) -> Option<String> {
+        if !matches!(self.r#type.as_str(), "function" | "event" | "error") {
+            return None;
+        }
+        let types: Vec<String> = self
+            .inputs
+            .as_ref()
+            .map(|inputs| inputs.iter().map(AbiInput::canonical_type).collect())
+            .unwrap_or_default();
+        Some(format!(
+            "{}({})",
+            self.name.as_deref().unwrap_or(""),
+            types.join(",")
+        ))
+    }
+
+    /// The 4-byte selector of a function or custom error.
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        if self.r#type == "event" {
+            return None;
+        }
+        let hash = keccak256(self.canonical_signature()?.as_bytes());
+        Some([hash[0], hash[1], hash[2], hash[3]])
+    }
+
+    /// The `topic0` hash of a non-anonymous event.
+    pub fn topic(&self) -> Option<[u8; 32]> {
+        if self.r#type != "event" || self.anonymous == Some(true) {
+            return None;
+        }
+        Some(keccak256(self.canonical_signature()?.as_bytes()))
+    }
+
+    /// Whether the item is a function that cannot modify state, including
+    /// legacy ABIs that only set `constant`.
+    pub fn is_read_only(&self) -> bool {
+        self.r#type == "function"
+            && (matches!(
+                self.state_mutability.as_deref(),
+                Some("view") | Some("pure")
+            ) || (self.state_mutability.is_none() && self.constant == Some(true)))
+    }
+
+    /// Serialises the item as a JSON ABI entry. Keys are always emitted in
+    /// the same order and only fields present on the item are written.
+    pub fn to_json(&self) -> JsonValue {
+        let mut entries = vec![("type".to_string(), JsonValue::string(&self.r#type))];
+        if let Some(name) = &self.name {
+            entries.push(("name".to_string(), JsonValue::string(name)));
+        }
+        if let Some(inputs) = &self.inputs {
+            entries.push((
+                "inputs".to_string(),
+                JsonValue::Array(inputs.iter().map(AbiInput::to_json).collect()),
+            ));
+        }
+        if let Some(outputs) = &self.outputs {
+            entries.push((
+                "outputs".to_string(),
+                JsonValue::Array(outputs.iter().map(AbiOutput::to_json).collect()),
+            ));
+        }
+        if let Some(state_mutability) = &self.state_mutability {
+            entries.push((
+                "stateMutability".to_string(),
+                JsonValue::string(state_mutability),
+            ));
+        }
+        if let Some(anonymous) = self.anonymous {
+            entries.push(("anonymous".to_string(), JsonValue::Bool(anonymous)));
+        }
+        if let Some(payable) = self.payable {
+            entries.push(("payable".to_string(), JsonValue::Bool(payable)));
+        }
+        if let Some(constant) = self.constant {
+            entries.push(("constant".to_string(), JsonValue::Bool(constant)));
+        }
+        JsonValue::Object(entries)
+    }
+}
+
 impl fmt::Display for AbiItem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let visibility = self.state_mutability.as_deref().unwrap_or("nonpayable");
@@ -60,7 +214,12 @@ impl fmt::Display for AbiItem {
                             .join(", ")
                     })
                     .unwrap_or_default();
-                write!(f, "constructor({params})")
+                let payable = if visibility == "payable" {
+                    " payable"
+                } else {
+                    ""
+                };
+                write!(f, "constructor({params}){payable}")
             }
             "event" => {
                 let params = self
@@ -85,9 +244,40 @@ impl fmt::Display for AbiItem {
                             .join(", ")
                     })
                     .unwrap_or_default();
+                let anonymous = if self.anonymous.unwrap_or(false) {
+                    " anonymous"
+                } else {
+                    ""
+                };
+                write!(
+                    f,
+                    "event {}({}){}",
+                    self.name.as_ref().unwrap_or(&String::new()),
+                    params,
+                    anonymous
+                )
+            }
+            "error" => {
+                let params = self
+                    .inputs
+                    .as_ref()
+                    .map(|inputs| {
+                        inputs
+                            .iter()
+                            .map(|p| {
+                                if let Some(name) = &p.name {
+                                    format!("{} {}", p.r#type, name)
+                                } else {
+                                    p.r#type.clone()
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
                 write!(
                     f,
-                    "event {}({})",
+                    "error {}({})",
                     self.name.as_ref().unwrap_or(&String::new()),
                     params
                 )
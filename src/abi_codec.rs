@@ -0,0 +1,247 @@
+use crate::abi::AbiOutput;
+
+/// An ABI type resolved from its `type` string and `components`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamType {
+    Uint,
+    Int,
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    pub fn from_output(output: &AbiOutput) -> Result<Self, String> {
+        let components = output
+            .components
+            .as_ref()
+            .map(|c| c.iter().map(ParamType::from_output).collect())
+            .transpose()?;
+        Self::parse(&output.r#type, components)
+    }
+
+    fn parse(r#type: &str, components: Option<Vec<ParamType>>) -> Result<Self, String> {
+        if let Some(open) = r#type.rfind('[') {
+            let inner = Self::parse(&r#type[..open], components)?;
+            let size = r#type[open + 1..]
+                .strip_suffix(']')
+                .ok_or_else(|| format!("Invalid type '{}'", r#type))?;
+            return if size.is_empty() {
+                Ok(ParamType::Array(Box::new(inner)))
+            } else {
+                let size = size
+                    .parse()
+                    .map_err(|_| format!("Invalid array size in '{}'", r#type))?;
+                Ok(ParamType::FixedArray(Box::new(inner), size))
+            };
+        }
+
+        match r#type {
+            "address" => Ok(ParamType::Address),
+            "bool" => Ok(ParamType::Bool),
+            "string" => Ok(ParamType::String),
+            "bytes" => Ok(ParamType::Bytes),
+            "tuple" => components
+                .map(ParamType::Tuple)
+                .ok_or_else(|| "Tuple type without components".to_string()),
+            t if t.starts_with("uint") => Ok(ParamType::Uint),
+            t if t.starts_with("int") => Ok(ParamType::Int),
+            t if t.starts_with("bytes") => t[5..]
+                .parse()
+                .ok()
+                .filter(|n| (1..=32).contains(n))
+                .map(ParamType::FixedBytes)
+                .ok_or_else(|| format!("Invalid type '{t}'")),
+            t => Err(format!("Unsupported type '{t}'")),
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(inner, _) => inner.is_dynamic(),
+            ParamType::Tuple(types) => types.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Size of the type's slot in the head of an enclosing tuple.
+    fn head_size(&self) -> usize {
+        if self.is_dynamic() {
+            return 32;
+        }
+        match self {
+            ParamType::FixedArray(inner, size) => inner.head_size() * size,
+            ParamType::Tuple(types) => types.iter().map(ParamType::head_size).sum(),
+            _ => 32,
+        }
+    }
+}
+
+/// Decodes ABI-encoded `data` as a tuple of `types`, formatting each value
+/// as text: integers in decimal, addresses and bytes as `0x` hex, arrays as
+/// `[a, b]` and tuples as `(a, b)`.
+pub fn decode_values(types: &[ParamType], data: &[u8]) -> Result<Vec<String>, String> {
+    decode_tuple(types, data, 0)
+}
+
+fn decode_tuple(types: &[ParamType], data: &[u8], base: usize) -> Result<Vec<String>, String> {
+    let mut values = Vec::with_capacity(types.len());
+    let mut head = base;
+
+    for param in types {
+        let value = if param.is_dynamic() {
+            decode_value(param, data, read_offset(data, head, base)?)?
+        } else {
+            decode_value(param, data, head)?
+        };
+        values.push(value);
+        head += param.head_size();
+    }
+
+    Ok(values)
+}
+
+fn decode_value(param: &ParamType, data: &[u8], at: usize) -> Result<String, String> {
+    match param {
+        ParamType::Uint => Ok(to_decimal(read_word(data, at)?)),
+        ParamType::Int => {
+            let word = read_word(data, at)?;
+            if word[0] & 0x80 == 0 {
+                Ok(to_decimal(word))
+            } else {
+                Ok(format!("-{}", to_decimal(twos_complement(word))))
+            }
+        }
+        ParamType::Address => Ok(to_hex(&read_word(data, at)?[12..])),
+        ParamType::Bool => Ok((read_word(data, at)?[31] != 0).to_string()),
+        ParamType::FixedBytes(size) => Ok(to_hex(&read_word(data, at)?[..*size])),
+        ParamType::Bytes => Ok(to_hex(read_bytes(data, at)?)),
+        ParamType::String => Ok(String::from_utf8_lossy(read_bytes(data, at)?).into_owned()),
+        ParamType::Array(inner) => {
+            let len = read_usize(data, at)?;
+            if len > data.len() {
+                return Err(format!("Array length {len} out of range at offset {at}"));
+            }
+            let types = vec![(**inner).clone(); len];
+            Ok(format!(
+                "[{}]",
+                decode_tuple(&types, data, at + 32)?.join(", ")
+            ))
+        }
+        ParamType::FixedArray(inner, len) => {
+            let types = vec![(**inner).clone(); *len];
+            Ok(format!("[{}]", decode_tuple(&types, data, at)?.join(", ")))
+        }
+        ParamType::Tuple(types) => Ok(format!("({})", decode_tuple(types, data, at)?.join(", "))),
+    }
+}
+
+pub fn read_word(data: &[u8], at: usize) -> Result<[u8; 32], String> {
+    let slice = data
+        .get(at..at + 32)
+        .ok_or_else(|| format!("Data too short: expected 32 bytes at offset {at}"))?;
+    let mut word = [0u8; 32];
+    word.copy_from_slice(slice);
+    Ok(word)
+}
+
+pub fn read_usize(data: &[u8], at: usize) -> Result<usize, String> {
+    let word = read_word(data, at)?;
+    if word[..24].iter().any(|&b| b != 0) {
+        return Err(format!("Offset or length out of range at offset {at}"));
+    }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&word[24..]);
+    Ok(u64::from_be_bytes(bytes) as usize)
+}
+
+/// Reads the offset stored at `at` and resolves it against `base`.
+pub fn read_offset(data: &[u8], at: usize, base: usize) -> Result<usize, String> {
+    let offset = read_usize(data, at)?;
+    if offset > data.len() {
+        return Err(format!("Offset {offset} out of range at offset {at}"));
+    }
+    Ok(base + offset)
+}
+
+pub fn read_bytes(data: &[u8], at: usize) -> Result<&[u8], String> {
+    let len = read_usize(data, at)?;
+    if len > data.len() {
+        return Err(format!("Length {len} out of range at offset {at}"));
+    }
+    data.get(at + 32..at + 32 + len)
+        .ok_or_else(|| format!("Data too short: expected {len} bytes at offset {}", at + 32))
+}
+
+/// Left-pads `value` into a 32-byte word.
+pub fn encode_usize(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn twos_complement(mut word: [u8; 32]) -> [u8; 32] {
+    for byte in word.iter_mut() {
+        *byte = !*byte;
+    }
+    for byte in word.iter_mut().rev() {
+        let (sum, overflow) = byte.overflowing_add(1);
+        *byte = sum;
+        if !overflow {
+            break;
+        }
+    }
+    word
+}
+
+/// Formats a big-endian 256-bit unsigned integer in decimal.
+fn to_decimal(mut word: [u8; 32]) -> String {
+    let mut digits = Vec::new();
+    while word.iter().any(|&b| b != 0) {
+        let mut remainder = 0u32;
+        for byte in word.iter_mut() {
+            let value = (remainder << 8) | *byte as u32;
+            *byte = (value / 10) as u8;
+            remainder = value % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits = hex.trim();
+    let digits = digits.strip_prefix("0x").unwrap_or(digits);
+    if !digits.is_ascii() {
+        return Err("Invalid hex string".to_string());
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("Hex string has an odd number of digits".to_string());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| format!("Invalid hex digit near offset {i}"))
+        })
+        .collect()
+}
@@ -0,0 +1,102 @@
+use crate::abi::{AbiInput, AbiItem, AbiOutput};
+
+/// Rewrites an ABI into a canonical form suitable for checking into git:
+/// items sorted by kind then name, legacy `constant`/`payable` flags folded
+/// into `stateMutability`, and every field a kind requires present with its
+/// default, so compiler-version noise doesn't show up in diffs.
+pub fn normalize_abi(abi: &[AbiItem]) -> Vec<AbiItem> {
+    let mut items: Vec<AbiItem> = abi
+        .iter()
+        .filter(|item| kind_rank(&item.r#type).is_some())
+        .map(normalize_item)
+        .collect();
+
+    items.sort_by(|a, b| {
+        kind_rank(&a.r#type)
+            .cmp(&kind_rank(&b.r#type))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.canonical_signature().cmp(&b.canonical_signature()))
+    });
+    items
+}
+
+/// Orders item kinds the way they read in a contract. Unknown kinds are
+/// dropped.
+pub fn kind_rank(kind: &str) -> Option<usize> {
+    [
+        "constructor",
+        "fallback",
+        "receive",
+        "function",
+        "event",
+        "error",
+    ]
+    .iter()
+    .position(|k| *k == kind)
+}
+
+/// Resolves an item's mutability, falling back to the pre-0.4.16 `constant`
+/// and `payable` flags when `stateMutability` is absent.
+pub fn effective_mutability(item: &AbiItem) -> &str {
+    match item.state_mutability.as_deref() {
+        Some(mutability) => mutability,
+        None if item.constant == Some(true) => "view",
+        None if item.payable == Some(true) || item.r#type == "receive" => "payable",
+        None => "nonpayable",
+    }
+}
+
+/// Normalises a single item without reordering anything around it.
+pub fn normalize_item(item: &AbiItem) -> AbiItem {
+    let kind = item.r#type.as_str();
+    let named = matches!(kind, "function" | "event" | "error");
+    let has_mutability = matches!(kind, "function" | "constructor" | "fallback" | "receive");
+
+    AbiItem {
+        r#type: item.r#type.clone(),
+        name: named.then(|| item.name.clone().unwrap_or_default()),
+        inputs: matches!(kind, "function" | "constructor" | "event" | "error").then(|| {
+            item.inputs
+                .iter()
+                .flatten()
+                .map(|input| normalize_input(input, kind == "event"))
+                .collect()
+        }),
+        outputs: (kind == "function").then(|| {
+            item.outputs
+                .iter()
+                .flatten()
+                .map(normalize_output)
+                .collect()
+        }),
+        state_mutability: has_mutability.then(|| effective_mutability(item).to_string()),
+        anonymous: (kind == "event").then_some(item.anonymous.unwrap_or(false)),
+        payable: None,
+        constant: None,
+    }
+}
+
+fn normalize_input(input: &AbiInput, event: bool) -> AbiInput {
+    AbiInput {
+        name: Some(input.name.clone().unwrap_or_default()),
+        r#type: input.r#type.clone(),
+        indexed: event.then_some(input.indexed.unwrap_or(false)),
+        internal_type: input.internal_type.clone(),
+        components: input
+            .components
+            .as_ref()
+            .map(|c| c.iter().map(|p| normalize_input(p, false)).collect()),
+    }
+}
+
+fn normalize_output(output: &AbiOutput) -> AbiOutput {
+    AbiOutput {
+        name: Some(output.name.clone().unwrap_or_default()),
+        r#type: output.r#type.clone(),
+        internal_type: output.internal_type.clone(),
+        components: output
+            .components
+            .as_ref()
+            .map(|c| c.iter().map(normalize_output).collect()),
+    }
+}
@@ -0,0 +1,219 @@
+use crate::abi::{AbiInput, AbiOutput};
+
+/// A parameter or struct member, independent of where it appears.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub r#type: String,
+    pub internal_type: Option<String>,
+    pub components: Vec<Param>,
+}
+
+impl From<&AbiInput> for Param {
+    fn from(input: &AbiInput) -> Self {
+        Param {
+            name: input.name.clone().unwrap_or_default(),
+            r#type: input.r#type.clone(),
+            internal_type: input.internal_type.clone(),
+            components: input.components.iter().flatten().map(Param::from).collect(),
+        }
+    }
+}
+
+impl From<&AbiOutput> for Param {
+    fn from(output: &AbiOutput) -> Self {
+        Param {
+            name: output.name.clone().unwrap_or_default(),
+            r#type: output.r#type.clone(),
+            internal_type: output.internal_type.clone(),
+            components: output
+                .components
+                .iter()
+                .flatten()
+                .map(Param::from)
+                .collect(),
+        }
+    }
+}
+
+/// The shape of a parameter type, for generators to spell in their own
+/// language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeShape {
+    /// An elementary ABI type such as `uint256` or `bytes`.
+    Elementary(String),
+    /// A tuple, by the name of the struct registered for it.
+    Struct(String),
+    /// `T[]` (`None`) or `T[k]`.
+    Array(Box<TypeShape>, Option<usize>),
+}
+
+/// A struct rebuilt from a tuple's `components`.
+pub struct StructDef {
+    pub name: String,
+    /// The qualified `internalType` name, such as `Exchange.Order`, that
+    /// identifies the struct.
+    pub qualified: String,
+    pub fields: Vec<Param>,
+}
+
+/// Struct definitions collected while generating one contract, in the order
+/// they are first referenced.
+#[derive(Default)]
+pub struct Structs {
+    pub defs: Vec<StructDef>,
+}
+
+impl Structs {
+    /// Returns the shape of `param`'s type, registering struct definitions
+    /// for tuples along the way. Structs are named after their
+    /// `internalType` when the compiler recorded one.
+    pub fn shape(&mut self, param: &Param) -> TypeShape {
+        let (base, dims) = split_array_suffix(&param.r#type);
+
+        let mut shape = if base == "tuple" {
+            TypeShape::Struct(self.register(param))
+        } else {
+            TypeShape::Elementary(base.to_string())
+        };
+        for dim in dims {
+            shape = TypeShape::Array(Box::new(shape), dim);
+        }
+        shape
+    }
+
+    /// Structs are named by the last segment of their `internalType`;
+    /// when two differ only in their qualifier, as `A.Info` and `B.Info`
+    /// do, the later one keeps the qualifier: `B_Info`.
+    fn register(&mut self, param: &Param) -> String {
+        let qualified = param
+            .internal_type
+            .as_deref()
+            .and_then(|t| t.strip_prefix("struct "))
+            .map(|t| t.split('[').next().unwrap_or(t).to_string())
+            .unwrap_or_else(|| format!("Struct{}", self.defs.len()));
+
+        if let Some(def) = self.defs.iter().find(|def| def.qualified == qualified) {
+            return def.name.clone();
+        }
+
+        let short = qualified.rsplit('.').next().unwrap_or(&qualified);
+        let mut taken: Vec<String> = self.defs.iter().map(|def| def.name.clone()).collect();
+        let name = if taken.iter().any(|name| name == short) {
+            unique(&mut taken, qualified.replace('.', "_"))
+        } else {
+            short.to_string()
+        };
+
+        // Reserve the slot before visiting members so dependencies are
+        // listed after the struct that uses them.
+        self.defs.push(StructDef {
+            name: name.clone(),
+            qualified,
+            fields: param.components.clone(),
+        });
+        for member in &param.components {
+            self.shape(member);
+        }
+        name
+    }
+}
+
+/// Splits `uint256[2][]` into `uint256` and its dimensions, innermost
+/// first: `[Some(2), None]`.
+fn split_array_suffix(r#type: &str) -> (&str, Vec<Option<usize>>) {
+    let base_end = r#type.find('[').unwrap_or(r#type.len());
+    let dims = r#type[base_end..]
+        .split(']')
+        .filter_map(|dim| dim.strip_prefix('['))
+        .map(|dim| dim.parse().ok())
+        .collect();
+    (&r#type[..base_end], dims)
+}
+
+/// Name to use for a parameter that has none, such as an unnamed output.
+pub fn param_name(param: &Param, fallback: &str, index: usize) -> String {
+    if param.name.is_empty() {
+        format!("{fallback}{index}")
+    } else {
+        param.name.clone()
+    }
+}
+
+/// Returns `name`, suffixed with a counter if an overload already took it.
+pub fn unique(taken: &mut Vec<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while taken.contains(&candidate) {
+        candidate = format!("{name}{counter}");
+        counter += 1;
+    }
+    taken.push(candidate.clone());
+    candidate
+}
+
+/// Splits an identifier into words at underscores and case changes.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = name.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // Split before a capital that follows a lowercase letter or digit
+        // (`ERC20Token`), or that starts a word after an acronym
+        // (`USDCToken`).
+        let boundary = c.is_ascii_uppercase()
+            && !current.is_empty()
+            && (!chars[i - 1].is_ascii_uppercase()
+                || chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase()));
+        if boundary {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `balanceOf` becomes `BalanceOf`, `DOMAIN_SEPARATOR` becomes
+/// `DomainSeparator`.
+pub fn upper_camel(name: &str) -> String {
+    let ident: String = words(name)
+        .iter()
+        .map(|word| {
+            let lower = word.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("X{ident}")
+    } else {
+        ident
+    }
+}
+
+/// `balanceOf` becomes `balance_of`, `ERC20Token` becomes `erc20_token`.
+pub fn snake_case(name: &str) -> String {
+    let ident = words(name)
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{ident}")
+    } else {
+        ident
+    }
+}
@@ -0,0 +1,365 @@
+use crate::abi_codec::{from_hex, to_hex};
+use crate::conformance::check_conformance;
+use crate::diff::{diff_abis, Change};
+use crate::file_ops::load_abi;
+use crate::git::diff_revisions;
+use crate::interfaces::{detect, Support};
+use crate::json_parser::ParseMode;
+use crate::json_writer::JsonValue;
+use crate::multicall::{decode_snapshot, encode_snapshot, snapshot_functions, MULTICALL3_ADDRESS};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Subcommands recognised as the first positional argument.
+const COMMANDS: &[&str] = &[
+    "multicall",
+    "multicall-decode",
+    "interfaces",
+    "check",
+    "diff",
+];
+
+/// Whether `positionals` start with a subcommand. A file or directory in
+/// `dir` named like one and given on its own is converted instead, so a
+/// `diff/` folder can still be passed as the input.
+pub fn is_command(dir: &Path, positionals: &[String]) -> bool {
+    match positionals {
+        [] => false,
+        [name] => COMMANDS.contains(&name.as_str()) && !dir.join(name).exists(),
+        [name, ..] => COMMANDS.contains(&name.as_str()),
+    }
+}
+
+pub struct CommandOptions {
+    pub raw: bool,
+    pub pretty: bool,
+    pub mode: ParseMode,
+    /// Reference ABI for `check --against`.
+    pub against: Option<String>,
+    /// Revision range for `diff --git`.
+    pub git: Option<String>,
+}
+
+pub fn run_command(name: &str, args: &[String], options: &CommandOptions) -> Result<(), String> {
+    match name {
+        "multicall" => run_multicall(args, options),
+        "multicall-decode" => run_multicall_decode(args, options),
+        "interfaces" => run_interfaces(args, options),
+        "check" => run_check(args, options),
+        "diff" => run_diff(args, options),
+        _ => Err(format!("Unknown command '{name}'")),
+    }
+}
+
+/// `multicall <abi> <address>`: prints `aggregate3` calldata that snapshots
+/// every zero-argument view function of the contract at `address`.
+fn run_multicall(args: &[String], options: &CommandOptions) -> Result<(), String> {
+    let [abi_path, address] = args else {
+        return Err("Usage: abi2human multicall <abi.json> <address>".to_string());
+    };
+
+    let abi = load_abi(Path::new(abi_path), options.mode)?;
+    let calldata = to_hex(&encode_snapshot(&abi, address)?);
+
+    if options.raw {
+        println!("{calldata}");
+    } else {
+        let calls = snapshot_functions(&abi)
+            .into_iter()
+            .filter_map(|f| f.canonical_signature())
+            .map(JsonValue::String)
+            .collect();
+        let json = JsonValue::Object(vec![
+            ("to".to_string(), JsonValue::string(MULTICALL3_ADDRESS)),
+            ("data".to_string(), JsonValue::String(calldata)),
+            ("calls".to_string(), JsonValue::Array(calls)),
+        ]);
+        println!("{}", json.render(options.pretty));
+    }
+
+    Ok(())
+}
+
+/// `multicall-decode <abi> [result]`: decodes the `aggregate3` return data,
+/// given as hex, a file containing hex, or on stdin.
+fn run_multicall_decode(args: &[String], options: &CommandOptions) -> Result<(), String> {
+    let (abi_path, result) = match args {
+        [abi_path] => (abi_path, None),
+        [abi_path, result] => (abi_path, Some(result)),
+        _ => {
+            return Err(
+                "Usage: abi2human multicall-decode <abi.json> [result-hex|file]".to_string(),
+            )
+        }
+    };
+
+    let abi = load_abi(Path::new(abi_path), options.mode)?;
+    let hex = match result {
+        Some(value) if value.starts_with("0x") => value.clone(),
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?
+        }
+        None => {
+            let mut content = String::new();
+            io::stdin()
+                .read_to_string(&mut content)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            content
+        }
+    };
+
+    let entries = decode_snapshot(&abi, &from_hex(&hex)?)?;
+
+    if options.raw {
+        let width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+        for entry in entries {
+            let value = if !entry.success {
+                format!("reverted {}", entry.values[0].1)
+            } else if entry.values.len() == 1 {
+                entry.values[0].1.clone()
+            } else {
+                entry
+                    .values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, value))| format!("{}={value}", output_label(name, i)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            println!("{:width$}  {value}", entry.name);
+        }
+    } else {
+        let rows = entries
+            .into_iter()
+            .map(|entry| {
+                let value = if !entry.success {
+                    JsonValue::Object(vec![(
+                        "reverted".to_string(),
+                        JsonValue::String(entry.values[0].1.clone()),
+                    )])
+                } else if entry.values.len() == 1 {
+                    JsonValue::String(entry.values[0].1.clone())
+                } else {
+                    JsonValue::Object(
+                        entry
+                            .values
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, (name, value))| {
+                                (output_label(&name, i), JsonValue::String(value))
+                            })
+                            .collect(),
+                    )
+                };
+                (entry.name, value)
+            })
+            .collect();
+        println!("{}", JsonValue::Object(rows).render(options.pretty));
+    }
+
+    Ok(())
+}
+
+fn output_label(name: &str, index: usize) -> String {
+    if name.is_empty() {
+        index.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `interfaces <abi>`: reports which built-in standard interfaces the ABI
+/// implements fully, partially or not at all.
+fn run_interfaces(args: &[String], options: &CommandOptions) -> Result<(), String> {
+    let [abi_path] = args else {
+        return Err("Usage: abi2human interfaces <abi.json>".to_string());
+    };
+
+    let abi = load_abi(Path::new(abi_path), options.mode)?;
+    let detected = detect(&abi);
+
+    if options.raw {
+        for (standard, support) in detected {
+            match support {
+                Support::Full => println!("{}: full", standard.name),
+                Support::Partial(missing) => println!(
+                    "{}: partial (missing {})",
+                    standard.name,
+                    missing.join(", ")
+                ),
+                Support::Absent => println!("{}: absent", standard.name),
+            }
+        }
+    } else {
+        let entries = detected
+            .into_iter()
+            .map(|(standard, support)| {
+                let (status, missing) = match support {
+                    Support::Full => ("full", Vec::new()),
+                    Support::Partial(missing) => ("partial", missing),
+                    Support::Absent => ("absent", Vec::new()),
+                };
+                JsonValue::Object(vec![
+                    ("standard".to_string(), JsonValue::string(standard.name)),
+                    ("support".to_string(), JsonValue::string(status)),
+                    (
+                        "missing".to_string(),
+                        JsonValue::Array(missing.into_iter().map(JsonValue::String).collect()),
+                    ),
+                ])
+            })
+            .collect();
+        println!("{}", JsonValue::Array(entries).render(options.pretty));
+    }
+
+    Ok(())
+}
+
+/// `check <abi> --against <reference>`: lists every function, event and
+/// error of the reference the implementation is missing or declares
+/// differently, failing when there is any.
+fn run_check(args: &[String], options: &CommandOptions) -> Result<(), String> {
+    let ([abi_path], Some(reference_path)) = (args, &options.against) else {
+        return Err("Usage: abi2human check <abi.json> --against <reference.json>".to_string());
+    };
+
+    let implementation = load_abi(Path::new(abi_path), options.mode)?;
+    let reference = load_abi(Path::new(reference_path), options.mode)?;
+    let issues = check_conformance(&implementation, &reference);
+
+    if options.raw {
+        for issue in &issues {
+            println!("{issue}");
+        }
+    } else {
+        let json = JsonValue::Object(vec![
+            ("conforms".to_string(), JsonValue::Bool(issues.is_empty())),
+            (
+                "issues".to_string(),
+                JsonValue::Array(issues.iter().map(|issue| issue.to_json()).collect()),
+            ),
+        ]);
+        println!("{}", json.render(options.pretty));
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{abi_path} does not conform to {reference_path} ({} issues)",
+            issues.len()
+        ))
+    }
+}
+
+/// `diff <old> <new>`: lists added, removed and changed functions, events
+/// and errors, failing when any change is breaking. With `--git <range>`,
+/// diffs every artifact under a path between two revisions instead.
+fn run_diff(args: &[String], options: &CommandOptions) -> Result<(), String> {
+    if let Some(range) = &options.git {
+        return run_diff_git(range, args, options);
+    }
+    let [old_path, new_path] = args else {
+        return Err(
+            "Usage: abi2human diff <old.json> <new.json>\n       abi2human diff --git <from>..<to> [path]"
+                .to_string(),
+        );
+    };
+
+    let old = load_abi(Path::new(old_path), options.mode)?;
+    let new = load_abi(Path::new(new_path), options.mode)?;
+    let changes = diff_abis(&old, &new);
+    print_changes(&changes, options);
+
+    match breaking_count(&changes) {
+        0 => Ok(()),
+        breaking => Err(format!("{breaking} breaking changes")),
+    }
+}
+
+fn print_changes(changes: &[Change], options: &CommandOptions) {
+    if options.raw {
+        for change in changes {
+            println!("{change}");
+        }
+    } else {
+        let json = JsonValue::Object(vec![
+            (
+                "breaking".to_string(),
+                JsonValue::Bool(breaking_count(changes) > 0),
+            ),
+            (
+                "changes".to_string(),
+                JsonValue::Array(changes.iter().map(Change::to_json).collect()),
+            ),
+        ]);
+        println!("{}", json.render(options.pretty));
+    }
+}
+
+/// `diff --git <range> [path]`: one summary per changed artifact, with its
+/// changes.
+fn run_diff_git(range: &str, args: &[String], options: &CommandOptions) -> Result<(), String> {
+    let path = match args {
+        [] => ".",
+        [path] => path.as_str(),
+        _ => return Err("Usage: abi2human diff --git <from>..<to> [path]".to_string()),
+    };
+
+    let artifacts = diff_revisions(Path::new("."), range, path, options.mode)?;
+    let total: usize = artifacts
+        .iter()
+        .map(|(_, changes)| breaking_count(changes))
+        .sum();
+
+    if options.raw {
+        for (file, changes) in &artifacts {
+            let breaking = breaking_count(changes);
+            println!(
+                "{file}: {breaking} breaking, {} compatible",
+                changes.len() - breaking
+            );
+            for change in changes {
+                println!("  {change}");
+            }
+        }
+    } else {
+        let contracts = artifacts
+            .iter()
+            .map(|(file, changes)| {
+                JsonValue::Object(vec![
+                    ("file".to_string(), JsonValue::string(file)),
+                    (
+                        "breaking".to_string(),
+                        JsonValue::Bool(breaking_count(changes) > 0),
+                    ),
+                    (
+                        "changes".to_string(),
+                        JsonValue::Array(changes.iter().map(Change::to_json).collect()),
+                    ),
+                ])
+            })
+            .collect();
+        let json = JsonValue::Object(vec![
+            ("breaking".to_string(), JsonValue::Bool(total > 0)),
+            ("contracts".to_string(), JsonValue::Array(contracts)),
+        ]);
+        println!("{}", json.render(options.pretty));
+    }
+
+    let contracts = artifacts
+        .iter()
+        .filter(|(_, changes)| breaking_count(changes) > 0)
+        .count();
+    match total {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{total} breaking changes in {contracts} contracts between {range}"
+        )),
+    }
+}
+
+fn breaking_count(changes: &[Change]) -> usize {
+    changes.iter().filter(|change| change.breaking).count()
+}
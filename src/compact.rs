@@ -0,0 +1,109 @@
+use crate::abi::{AbiInput, AbiItem};
+use crate::canonical::effective_mutability;
+
+/// Rough bytes-per-token ratio of BPE tokenizers on ABI-like text.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Renders an ABI in the densest notation that still tells items apart:
+/// no `function` keyword, no spaces after commas, `→` for returns, and
+/// items grouped under `## view`, `## write` and similar headers instead
+/// of repeating mutability on every line.
+pub fn render_compact(abi: &[AbiItem], names: bool) -> String {
+    let mut groups: Vec<(&str, Vec<String>)> = [
+        "constructor",
+        "view",
+        "pure",
+        "write",
+        "payable",
+        "event",
+        "error",
+    ]
+    .iter()
+    .map(|group| (*group, Vec::new()))
+    .collect();
+
+    for item in abi {
+        let group = match item.r#type.as_str() {
+            "constructor" => "constructor",
+            "event" => "event",
+            "error" => "error",
+            "function" | "fallback" | "receive" => match effective_mutability(item) {
+                "view" => "view",
+                "pure" => "pure",
+                "payable" => "payable",
+                _ => "write",
+            },
+            _ => continue,
+        };
+        if let Some((_, lines)) = groups.iter_mut().find(|(name, _)| *name == group) {
+            lines.push(compact_item(item, names));
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, lines)| !lines.is_empty())
+        .map(|(group, lines)| format!("## {group}\n{}", lines.join("\n")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn compact_item(item: &AbiItem, names: bool) -> String {
+    let name = match item.r#type.as_str() {
+        "function" | "event" | "error" => item.name.as_deref().unwrap_or(""),
+        kind => kind,
+    };
+    let params: Vec<String> = item
+        .inputs
+        .iter()
+        .flatten()
+        .map(|input| compact_param(input, names))
+        .collect();
+    let mut line = format!("{name}({})", params.join(","));
+
+    if item.anonymous == Some(true) {
+        line.push_str(" anonymous");
+    }
+
+    let outputs: Vec<String> = item
+        .outputs
+        .iter()
+        .flatten()
+        .map(|output| {
+            let name = output.name.as_deref().filter(|n| names && !n.is_empty());
+            match name {
+                Some(name) => format!("{} {name}", output.canonical_type()),
+                None => output.canonical_type(),
+            }
+        })
+        .collect();
+    match outputs.as_slice() {
+        [] => {}
+        [single] => line.push_str(&format!("→{single}")),
+        _ => line.push_str(&format!("→({})", outputs.join(","))),
+    }
+    line
+}
+
+fn compact_param(input: &AbiInput, names: bool) -> String {
+    let mut param = input.canonical_type();
+    if input.indexed == Some(true) {
+        param.push_str(" indexed");
+    }
+    if let Some(name) = input.name.as_deref().filter(|n| names && !n.is_empty()) {
+        param.push(' ');
+        param.push_str(name);
+    }
+    param
+}
+
+/// Summarises how much smaller the compact output is than its input.
+pub fn savings_summary(input_bytes: usize, output_bytes: usize) -> String {
+    let saved = input_bytes.saturating_sub(output_bytes);
+    let percent = (saved * 100).checked_div(input_bytes).unwrap_or(0);
+    format!(
+        "📉 {input_bytes} → {output_bytes} bytes ({percent}% smaller, ~{} → ~{} tokens)",
+        input_bytes.div_ceil(BYTES_PER_TOKEN),
+        output_bytes.div_ceil(BYTES_PER_TOKEN)
+    )
+}
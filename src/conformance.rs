@@ -0,0 +1,186 @@
+use crate::abi::AbiItem;
+use crate::canonical::effective_mutability;
+use crate::json_writer::JsonValue;
+use std::fmt;
+
+/// A reference item the implementation does not conform to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// `function`, `event` or `error`.
+    pub kind: String,
+    /// The reference item's canonical signature.
+    pub signature: String,
+    /// `missing`, `signature`, `mutability`, `returns` or `indexed`.
+    pub problem: &'static str,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+impl Issue {
+    pub fn to_json(&self) -> JsonValue {
+        let mut entries = vec![
+            ("kind".to_string(), JsonValue::string(&self.kind)),
+            ("signature".to_string(), JsonValue::string(&self.signature)),
+            ("problem".to_string(), JsonValue::string(self.problem)),
+        ];
+        if let Some(expected) = &self.expected {
+            entries.push(("expected".to_string(), JsonValue::string(expected)));
+        }
+        if let Some(found) = &self.found {
+            entries.push(("found".to_string(), JsonValue::string(found)));
+        }
+        JsonValue::Object(entries)
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: ", self.kind, self.signature)?;
+        match (self.problem, &self.expected, &self.found) {
+            ("missing", _, _) => write!(f, "missing"),
+            (problem, Some(expected), Some(found)) => {
+                write!(f, "{problem} differs, expected {expected}, found {found}")
+            }
+            (problem, _, _) => write!(f, "{problem} differs"),
+        }
+    }
+}
+
+/// Checks that `implementation` provides every function, event and error
+/// of `reference` with the same signature, a compatible state mutability,
+/// the same return types and, for events, the same indexed parameters.
+///
+/// A view or pure implementation of a nonpayable function, or a pure one of
+/// a view function, is compatible: callers can make the same calls.
+pub fn check_conformance(implementation: &[AbiItem], reference: &[AbiItem]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for expected in reference {
+        if !matches!(expected.r#type.as_str(), "function" | "event" | "error") {
+            continue;
+        }
+        let Some(signature) = expected.canonical_signature() else {
+            continue;
+        };
+        let same_kind: Vec<&AbiItem> = implementation
+            .iter()
+            .filter(|item| item.r#type == expected.r#type)
+            .collect();
+        let Some(found) = same_kind
+            .iter()
+            .find(|item| item.canonical_signature().as_deref() == Some(signature.as_str()))
+        else {
+            // An item of the same name with other parameter types is a
+            // changed signature rather than a missing item.
+            let overloads: Vec<String> = same_kind
+                .iter()
+                .filter(|item| item.name == expected.name)
+                .filter_map(|item| item.canonical_signature())
+                .collect();
+            issues.push(if overloads.is_empty() {
+                new_issue(expected, &signature, "missing", None, None)
+            } else {
+                new_issue(
+                    expected,
+                    &signature,
+                    "signature",
+                    Some(signature.clone()),
+                    Some(overloads.join(", ")),
+                )
+            });
+            continue;
+        };
+
+        match expected.r#type.as_str() {
+            "function" => {
+                let (want, have) = (effective_mutability(expected), effective_mutability(found));
+                if !mutability_compatible(want, have) {
+                    issues.push(new_issue(
+                        expected,
+                        &signature,
+                        "mutability",
+                        Some(want.to_string()),
+                        Some(have.to_string()),
+                    ));
+                }
+                let (want, have) = (return_types(expected), return_types(found));
+                if want != have {
+                    issues.push(new_issue(
+                        expected,
+                        &signature,
+                        "returns",
+                        Some(want),
+                        Some(have),
+                    ));
+                }
+            }
+            "event" => {
+                let (want, have) = (indexed_params(expected), indexed_params(found));
+                if want != have {
+                    issues.push(new_issue(
+                        expected,
+                        &signature,
+                        "indexed",
+                        Some(want),
+                        Some(have),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+fn new_issue(
+    item: &AbiItem,
+    signature: &str,
+    problem: &'static str,
+    expected: Option<String>,
+    found: Option<String>,
+) -> Issue {
+    Issue {
+        kind: item.r#type.clone(),
+        signature: signature.to_string(),
+        problem,
+        expected,
+        found,
+    }
+}
+
+/// Whether a function implemented as `found` can stand in for one declared
+/// as `expected`.
+pub fn mutability_compatible(expected: &str, found: &str) -> bool {
+    expected == found
+        || (expected == "nonpayable" && matches!(found, "view" | "pure"))
+        || (expected == "view" && found == "pure")
+}
+
+/// A function's return types as `(uint256,bool)`.
+pub fn return_types(item: &AbiItem) -> String {
+    let types: Vec<String> = item
+        .outputs
+        .iter()
+        .flatten()
+        .map(|output| output.canonical_type())
+        .collect();
+    format!("({})", types.join(","))
+}
+
+/// An event's parameters with their `indexed` flags, as
+/// `(address indexed,uint256)`.
+pub fn indexed_params(item: &AbiItem) -> String {
+    let params: Vec<String> = item
+        .inputs
+        .iter()
+        .flatten()
+        .map(|input| {
+            if input.indexed == Some(true) {
+                format!("{} indexed", input.canonical_type())
+            } else {
+                input.canonical_type()
+            }
+        })
+        .collect();
+    format!("({})", params.join(","))
+}
@@ -1,14 +1,167 @@
 use crate::abi::AbiItem;
-use crate::json_parser::JsonParser;
+use crate::canonical::normalize_abi;
+use crate::compact::render_compact;
+use crate::dedupe::Hoisted;
+use crate::filter::Filter;
+use crate::go::render_go;
+use crate::html::render_report;
+use crate::interfaces::summary;
+use crate::json_parser::{JsonParser, ParseMode};
+use crate::json_writer::JsonValue;
+use crate::layout::{group_items, sort_items, Layout};
+use crate::markdown::render_markdown;
+use crate::natspec::NatSpec;
+use crate::python::render_python;
+use crate::rust::render_bindings;
+use crate::signature_parser::parse_signatures;
+use crate::solidity::render_interfaces;
+use crate::structured::{render_lines, render_structured, render_structured_sections};
+use crate::typescript::render_module;
+
+/// The document produced for each converted ABI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// JSON array of human-readable signatures.
+    #[default]
+    Json,
+    /// One human-readable signature per line.
+    Raw,
+    /// JSON array of objects describing each item.
+    Structured,
+    /// JSON Lines: one structured object per item, tagged with its
+    /// contract and source file.
+    Ndjson,
+    /// Normalised JSON ABI with stable ordering.
+    Canonical,
+    /// Solidity `interface` source.
+    Solidity,
+    /// Markdown documentation of the contract surface.
+    Markdown,
+    /// Self-contained HTML report.
+    Html,
+    /// TypeScript module exporting the ABI `as const`.
+    TypeScript,
+    /// Dependency-free Rust bindings.
+    Rust,
+    /// Python module of selectors, topics and struct dataclasses.
+    Python,
+    /// Go file of selector and topic constants and structs.
+    Go,
+    /// Densest readable notation, grouped by mutability, optionally
+    /// without parameter names.
+    Compact { names: bool },
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "json" => Ok(OutputFormat::Json),
+            "raw" | "text" => Ok(OutputFormat::Raw),
+            "structured" => Ok(OutputFormat::Structured),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            "canonical" | "abi" => Ok(OutputFormat::Canonical),
+            "solidity" | "sol" => Ok(OutputFormat::Solidity),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "typescript" | "ts" => Ok(OutputFormat::TypeScript),
+            "rust" | "rs" => Ok(OutputFormat::Rust),
+            "python" | "py" => Ok(OutputFormat::Python),
+            "go" => Ok(OutputFormat::Go),
+            "compact" => Ok(OutputFormat::Compact { names: true }),
+            _ => Err(format!(
+                "Unknown format '{name}' (expected json, raw, structured, ndjson, canonical, \
+                 solidity, markdown, html, typescript, rust, python, go or compact)"
+            )),
+        }
+    }
+
+    /// Whether `--group` splits this format's output into labelled groups.
+    pub fn groupable(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Json | OutputFormat::Structured | OutputFormat::Raw
+        )
+    }
+
+    /// Whether a directory conversion writes one document for every
+    /// contract instead of one file per input.
+    pub fn combined(self) -> bool {
+        matches!(self, OutputFormat::Html | OutputFormat::Ndjson)
+    }
+
+    /// Whether this format renders the NatSpec of a contract.
+    pub fn documented(self) -> bool {
+        matches!(self, OutputFormat::Markdown | OutputFormat::Html)
+    }
+
+    /// File extension used when output paths are derived from input paths.
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json | OutputFormat::Structured | OutputFormat::Canonical => "json",
+            OutputFormat::Raw | OutputFormat::Compact { .. } => "txt",
+            OutputFormat::Ndjson => "jsonl",
+            OutputFormat::Solidity => "sol",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+            OutputFormat::TypeScript => "ts",
+            OutputFormat::Rust => "rs",
+            OutputFormat::Python => "py",
+            OutputFormat::Go => "go",
+        }
+    }
+}
+
+/// A named ABI together with the NatSpec it was loaded with.
+pub struct Contract {
+    pub name: String,
+    pub abi: Vec<AbiItem>,
+    pub docs: NatSpec,
+}
 
 pub struct Converter;
 
 impl Converter {
+    #[cfg(test)]
     pub fn parse_abi_content(content: &str) -> Result<Vec<AbiItem>, String> {
-        let mut parser = JsonParser::new(content);
+        Self::parse_abi_content_with_mode(content, ParseMode::default())
+    }
+
+    pub fn parse_abi_content_with_mode(
+        content: &str,
+        mode: ParseMode,
+    ) -> Result<Vec<AbiItem>, String> {
+        let mut parser = JsonParser::with_mode(content, mode);
         parser.parse_abi()
     }
 
+    /// Reads the NatSpec of a compiler artifact for output in `format`.
+    /// Content without any, or that fails to parse, yields an empty
+    /// `NatSpec`, as does a format that does not render it; the artifact is
+    /// then not parsed a second time.
+    pub fn parse_natspec(content: &str, mode: ParseMode, format: OutputFormat) -> NatSpec {
+        if !format.documented() {
+            return NatSpec::default();
+        }
+        JsonParser::with_mode(content, mode)
+            .parse_natspec()
+            .unwrap_or_default()
+    }
+
+    /// Parses human-readable signatures, given either one per line or as a
+    /// JSON array of strings, back into ABI items.
+    pub fn parse_human_readable(content: &str) -> Result<Vec<AbiItem>, String> {
+        if content.trim_start().starts_with('[') {
+            let lines = JsonParser::new(content).parse_string_array()?;
+            parse_signatures(&lines)
+        } else {
+            parse_signatures(&content.lines().collect::<Vec<_>>())
+        }
+    }
+
+    pub fn format_as_abi_json(abi: &[AbiItem], pretty: bool) -> String {
+        JsonValue::Array(abi.iter().map(AbiItem::to_json).collect()).render(pretty)
+    }
+
     pub fn convert_to_human_readable(abi: &[AbiItem]) -> Vec<String> {
         abi.iter()
             .filter(|item| !item.r#type.is_empty() && item.r#type != "unknown")
@@ -17,6 +170,236 @@ impl Converter {
             .collect()
     }
 
+    /// Renders one contract's ABI in `format`, without a trailing newline.
+    /// `name` is used by formats that declare a named type, such as
+    /// Solidity interfaces, and `docs` by formats that render NatSpec.
+    pub fn format_items(
+        name: &str,
+        abi: &[AbiItem],
+        docs: &NatSpec,
+        format: OutputFormat,
+        pretty: bool,
+    ) -> String {
+        match format {
+            OutputFormat::Json => {
+                Self::format_as_json_array(&Self::convert_to_human_readable(abi), pretty)
+            }
+            OutputFormat::Raw => Self::convert_to_human_readable(abi).join("\n"),
+            OutputFormat::Structured => render_structured(abi, pretty),
+            OutputFormat::Ndjson => render_lines(None, name, abi),
+            OutputFormat::Canonical => Self::format_as_abi_json(&normalize_abi(abi), pretty),
+            OutputFormat::Solidity => render_interfaces(&[(name.to_string(), abi.to_vec())]),
+            OutputFormat::Markdown => render_markdown(name, abi, docs),
+            OutputFormat::Html => render_report(
+                name,
+                &[Contract {
+                    name: name.to_string(),
+                    abi: abi.to_vec(),
+                    docs: docs.clone(),
+                }],
+            ),
+            OutputFormat::TypeScript => render_module(&[(name.to_string(), abi.to_vec())], pretty),
+            OutputFormat::Rust => render_bindings(&[(name.to_string(), abi.to_vec())]),
+            OutputFormat::Python => render_python(&[(name.to_string(), abi.to_vec())]),
+            OutputFormat::Go => render_go(&[(name.to_string(), abi.to_vec())]),
+            OutputFormat::Compact { names } => render_compact(abi, names),
+        }
+    }
+
+    /// Renders the items of one contract that `filter` selects, after
+    /// applying `layout`: items are sorted, and json, structured and raw
+    /// output are split into labelled groups when asked for. Raw and compact
+    /// output start with a `//` line naming the standard interfaces the
+    /// whole ABI implements, unless the filter leaves no items.
+    pub fn format_contract(
+        name: &str,
+        abi: &[AbiItem],
+        docs: &NatSpec,
+        format: OutputFormat,
+        pretty: bool,
+        layout: Layout,
+        filter: &Filter,
+    ) -> String {
+        let items = sort_items(&filter.apply(abi), layout.sort);
+        let formatted = if layout.group && format.groupable() {
+            Self::format_sections(&group_items(&items), format, pretty)
+        } else {
+            Self::format_items(name, &items, docs, format, pretty)
+        };
+        // The header describes the unfiltered ABI, so it is left out when
+        // the filter keeps nothing for it to sit above.
+        match summary(abi) {
+            Some(line)
+                if !items.is_empty()
+                    && matches!(format, OutputFormat::Raw | OutputFormat::Compact { .. }) =>
+            {
+                format!("// Whole ABI implements {line}\n{formatted}")
+            }
+            _ => formatted,
+        }
+    }
+
+    /// `format_contract` for several named ABIs. Grouped sections are
+    /// labelled `name: group`.
+    pub fn format_exports(
+        sections: &[(String, Vec<AbiItem>)],
+        format: OutputFormat,
+        pretty: bool,
+        layout: Layout,
+    ) -> String {
+        let grouped = layout.group && format.groupable();
+        let sections: Vec<(String, Vec<AbiItem>)> = sections
+            .iter()
+            .flat_map(|(name, abi)| {
+                let items = sort_items(abi, layout.sort);
+                if grouped {
+                    group_items(&items)
+                        .into_iter()
+                        .map(|(group, items)| (format!("{name}: {group}"), items))
+                        .collect()
+                } else {
+                    vec![(name.clone(), items)]
+                }
+            })
+            .collect();
+        Self::format_sections(&sections, format, pretty)
+    }
+
+    /// `format_exports` for a deduplicated document. JSON output stays
+    /// keyed by contract name:
+    /// `{"common": {"common": [...]}, "contracts": {"Token": {"uses":
+    /// ["common"], "items": [...]}}}`. Raw and compact output list the
+    /// `common` sections first and head each contract with the sets it
+    /// uses.
+    pub fn format_hoisted(
+        hoisted: &Hoisted,
+        format: OutputFormat,
+        pretty: bool,
+        layout: Layout,
+    ) -> String {
+        if format != OutputFormat::Json {
+            return Self::format_exports(&hoisted.sections(), format, pretty, layout);
+        }
+
+        let lines = |items: &[AbiItem]| {
+            JsonValue::Array(
+                Self::convert_to_human_readable(items)
+                    .iter()
+                    .map(|line| JsonValue::string(line))
+                    .collect(),
+            )
+        };
+        let section = |abi: &[AbiItem]| {
+            let items = sort_items(abi, layout.sort);
+            if layout.group {
+                JsonValue::Object(
+                    group_items(&items)
+                        .into_iter()
+                        .map(|(group, items)| (group, lines(&items)))
+                        .collect(),
+                )
+            } else {
+                lines(&items)
+            }
+        };
+
+        let common = hoisted
+            .common
+            .iter()
+            .map(|(name, abi)| (name.clone(), section(abi)))
+            .collect();
+        let contracts = hoisted
+            .contracts
+            .iter()
+            .map(|contract| {
+                let uses = contract.uses.iter().map(|set| JsonValue::string(set));
+                (
+                    contract.name.clone(),
+                    JsonValue::Object(vec![
+                        ("uses".to_string(), JsonValue::Array(uses.collect())),
+                        ("items".to_string(), section(&contract.items)),
+                    ]),
+                )
+            })
+            .collect();
+        JsonValue::Object(vec![
+            ("common".to_string(), JsonValue::Object(common)),
+            ("contracts".to_string(), JsonValue::Object(contracts)),
+        ])
+        .render(pretty)
+    }
+
+    /// Renders several named ABIs, such as the exports of a TypeScript
+    /// module, as a single document.
+    pub fn format_sections(
+        sections: &[(String, Vec<AbiItem>)],
+        format: OutputFormat,
+        pretty: bool,
+    ) -> String {
+        match format {
+            OutputFormat::Json => {
+                let readable: Vec<(String, Vec<String>)> = sections
+                    .iter()
+                    .map(|(name, abi)| (name.clone(), Self::convert_to_human_readable(abi)))
+                    .collect();
+                Self::format_as_json_object(&readable, pretty)
+            }
+            OutputFormat::Raw => sections
+                .iter()
+                .map(|(name, abi)| {
+                    format!(
+                        "// {name}\n{}",
+                        Self::format_items(name, abi, &NatSpec::default(), format, pretty)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            OutputFormat::Structured => render_structured_sections(sections, pretty),
+            OutputFormat::Ndjson => sections
+                .iter()
+                .map(|(name, abi)| render_lines(None, name, abi))
+                .filter(|lines| !lines.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            OutputFormat::Canonical => JsonValue::Object(
+                sections
+                    .iter()
+                    .map(|(name, abi)| {
+                        let items = normalize_abi(abi).iter().map(AbiItem::to_json).collect();
+                        (name.clone(), JsonValue::Array(items))
+                    })
+                    .collect(),
+            )
+            .render(pretty),
+            OutputFormat::Solidity => render_interfaces(sections),
+            OutputFormat::Markdown => sections
+                .iter()
+                .map(|(name, abi)| render_markdown(name, abi, &NatSpec::default()))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            OutputFormat::Html => {
+                let contracts: Vec<Contract> = sections
+                    .iter()
+                    .map(|(name, abi)| Contract {
+                        name: name.clone(),
+                        abi: abi.clone(),
+                        docs: NatSpec::default(),
+                    })
+                    .collect();
+                render_report("ABI Report", &contracts)
+            }
+            OutputFormat::TypeScript => render_module(sections, pretty),
+            OutputFormat::Rust => render_bindings(sections),
+            OutputFormat::Python => render_python(sections),
+            OutputFormat::Go => render_go(sections),
+            OutputFormat::Compact { names } => sections
+                .iter()
+                .map(|(name, abi)| format!("# {name}\n{}", render_compact(abi, names)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
     pub fn format_as_json_array(human_readable: &[String], pretty: bool) -> String {
         if pretty {
             format_json_pretty(human_readable)
@@ -24,6 +407,33 @@ impl Converter {
             format_json_compact(human_readable)
         }
     }
+
+    /// Formats named groups of readable lines as a JSON object of arrays,
+    /// preserving the order of `sections`.
+    pub fn format_as_json_object(sections: &[(String, Vec<String>)], pretty: bool) -> String {
+        if sections.is_empty() {
+            return "{}".to_string();
+        }
+
+        let entries: Vec<String> = sections
+            .iter()
+            .map(|(name, lines)| {
+                let array = Self::format_as_json_array(lines, pretty);
+                let key = escape_json_string(name);
+                if pretty {
+                    format!("  \"{key}\": {}", array.replace('\n', "\n  "))
+                } else {
+                    format!("\"{key}\":{array}")
+                }
+            })
+            .collect();
+
+        if pretty {
+            format!("{{\n{}\n}}", entries.join(",\n"))
+        } else {
+            format!("{{{}}}", entries.join(","))
+        }
+    }
 }
 
 fn format_json_pretty(strings: &[String]) -> String {
@@ -53,7 +463,7 @@ fn format_json_compact(strings: &[String]) -> String {
     format!("[{}]", escaped.join(","))
 }
 
-fn escape_json_string(s: &str) -> String {
+pub fn escape_json_string(s: &str) -> String {
     let mut result = String::new();
     for c in s.chars() {
         match c {
@@ -0,0 +1,127 @@
+use crate::abi::AbiItem;
+use crate::canonical::normalize_item;
+use std::collections::{HashMap, HashSet};
+
+/// A combined document with shared items hoisted out of the contracts.
+pub struct Hoisted {
+    /// Items shared by a set of contracts, named `common` (`common 1`,
+    /// `common 2`, ... when there are several).
+    pub common: Vec<(String, Vec<AbiItem>)>,
+    pub contracts: Vec<HoistedContract>,
+}
+
+/// A contract's remaining items and the `common` sets it also uses.
+pub struct HoistedContract {
+    pub name: String,
+    pub uses: Vec<String>,
+    pub items: Vec<AbiItem>,
+}
+
+impl Hoisted {
+    /// Named sections for text output: the `common` sets first, then each
+    /// contract headed by the sets it uses, as `Token + common 1, common 2`.
+    pub fn sections(&self) -> Vec<(String, Vec<AbiItem>)> {
+        let contracts = self.contracts.iter().map(|contract| {
+            let name = if contract.uses.is_empty() {
+                contract.name.clone()
+            } else {
+                format!("{} + {}", contract.name, contract.uses.join(", "))
+            };
+            (name, contract.items.clone())
+        });
+        self.common.iter().cloned().chain(contracts).collect()
+    }
+}
+
+/// Hoists functions, events and errors that appear identically in more
+/// than one section into shared sets, so inherited interfaces such as
+/// `Ownable` are listed once.
+///
+/// Shared items are grouped by the exact set of sections that contain
+/// them, and every contract records which sets it uses.
+pub fn hoist_common(sections: &[(String, Vec<AbiItem>)]) -> Hoisted {
+    // Each distinct item with the indices of the sections it appears in,
+    // found by identity so large trees stay linear.
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut items: Vec<(String, AbiItem, Vec<usize>)> = Vec::new();
+    for (index, (_, abi)) in sections.iter().enumerate() {
+        for item in abi.iter().filter(|item| shareable(item)) {
+            let key = identity(item);
+            match positions.get(&key) {
+                Some(&position) => {
+                    let owners = &mut items[position].2;
+                    if owners.last() != Some(&index) {
+                        owners.push(index);
+                    }
+                }
+                None => {
+                    positions.insert(key.clone(), items.len());
+                    items.push((key, item.clone(), vec![index]));
+                }
+            }
+        }
+    }
+
+    let mut set_positions: HashMap<Vec<usize>, usize> = HashMap::new();
+    let mut sets: Vec<(Vec<usize>, Vec<AbiItem>)> = Vec::new();
+    let mut shared = HashSet::new();
+    for (key, item, owners) in items.into_iter().filter(|(_, _, owners)| owners.len() > 1) {
+        match set_positions.get(&owners) {
+            Some(&position) => sets[position].1.push(item),
+            None => {
+                set_positions.insert(owners.clone(), sets.len());
+                sets.push((owners, vec![item]));
+            }
+        }
+        shared.insert(key);
+    }
+
+    let label = |set: usize| {
+        if sets.len() == 1 {
+            "common".to_string()
+        } else {
+            format!("common {}", set + 1)
+        }
+    };
+
+    let mut uses = vec![Vec::new(); sections.len()];
+    for (set, (owners, _)) in sets.iter().enumerate() {
+        for &owner in owners {
+            uses[owner].push(label(set));
+        }
+    }
+
+    let common = sets
+        .iter()
+        .enumerate()
+        .map(|(set, (_, items))| (label(set), items.clone()))
+        .collect();
+
+    let contracts = sections
+        .iter()
+        .zip(uses)
+        .map(|((name, abi), uses)| HoistedContract {
+            name: name.clone(),
+            uses,
+            items: abi
+                .iter()
+                .filter(|item| !shareable(item) || !shared.contains(&identity(item)))
+                .cloned()
+                .collect(),
+        })
+        .collect();
+
+    Hoisted { common, contracts }
+}
+
+/// Constructors and fallbacks belong to their contract even when they
+/// match another's.
+fn shareable(item: &AbiItem) -> bool {
+    matches!(item.r#type.as_str(), "function" | "event" | "error")
+}
+
+/// Items are identical when their normalised JSON is, so parameter names,
+/// mutability and `indexed` flags all have to match.
+fn identity(item: &AbiItem) -> String {
+    normalize_item(item).to_json().render(false)
+}
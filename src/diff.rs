@@ -0,0 +1,213 @@
+use crate::abi::AbiItem;
+use crate::canonical::effective_mutability;
+use crate::conformance::{indexed_params, return_types};
+use crate::json_writer::JsonValue;
+use std::fmt;
+
+/// One difference between two versions of an ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// `added`, `removed` or `changed`.
+    pub change: &'static str,
+    /// `function`, `event`, `error`, `fallback` or `receive`.
+    pub kind: String,
+    /// The canonical signature in the old ABI, or the new one for added
+    /// items.
+    pub signature: String,
+    /// What changed, such as `mutability payable → nonpayable`.
+    pub details: Vec<String>,
+    pub breaking: bool,
+}
+
+impl Change {
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            ("change".to_string(), JsonValue::string(self.change)),
+            ("kind".to_string(), JsonValue::string(&self.kind)),
+            ("signature".to_string(), JsonValue::string(&self.signature)),
+            ("breaking".to_string(), JsonValue::Bool(self.breaking)),
+            (
+                "details".to_string(),
+                JsonValue::Array(self.details.iter().map(|d| JsonValue::string(d)).collect()),
+            ),
+        ])
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.change {
+            "added" => '+',
+            "removed" => '-',
+            _ => '~',
+        };
+        write!(f, "{marker} {} {}", self.kind, self.signature)?;
+        if !self.details.is_empty() {
+            write!(f, ": {}", self.details.join("; "))?;
+        }
+        if self.breaking {
+            write!(f, " [breaking]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two ABIs item by item.
+///
+/// Items are paired by canonical signature, then unpaired items of the same
+/// kind and name are paired as changed signatures. Breaking changes are
+/// those that can make existing callers or indexers fail: removing a
+/// function, event, fallback or receive; changing parameter or return
+/// types; changing `indexed` or `anonymous`; and tightening mutability
+/// (payable to non-payable, or view/pure to state-changing). Additions,
+/// removed errors, renamed parameters and loosened mutability are
+/// compatible.
+pub fn diff_abis(old: &[AbiItem], new: &[AbiItem]) -> Vec<Change> {
+    let old: Vec<&AbiItem> = old.iter().filter(|item| compared(item)).collect();
+    let new: Vec<&AbiItem> = new.iter().filter(|item| compared(item)).collect();
+    let mut paired = vec![false; new.len()];
+    let mut unmatched = Vec::new();
+    let mut changes = Vec::new();
+
+    for before in &old {
+        let wanted = key(before);
+        match (0..new.len()).find(|&i| !paired[i] && key(new[i]) == wanted) {
+            Some(i) => {
+                paired[i] = true;
+                changes.extend(compare(before, new[i]));
+            }
+            None => unmatched.push(*before),
+        }
+    }
+
+    for before in unmatched {
+        let renamed = (0..new.len())
+            .find(|&i| !paired[i] && new[i].r#type == before.r#type && new[i].name == before.name);
+        match renamed {
+            Some(i) => {
+                paired[i] = true;
+                let mut change = compare(before, new[i]).unwrap_or_else(|| changed(before));
+                change.details.insert(
+                    0,
+                    format!("parameters {} → {}", signature(before), signature(new[i])),
+                );
+                change.breaking = true;
+                changes.push(change);
+            }
+            None => changes.push(Change {
+                change: "removed",
+                kind: before.r#type.clone(),
+                signature: signature(before),
+                details: Vec::new(),
+                breaking: before.r#type != "error",
+            }),
+        }
+    }
+
+    for (i, after) in new.iter().enumerate() {
+        if !paired[i] {
+            changes.push(Change {
+                change: "added",
+                kind: after.r#type.clone(),
+                signature: signature(after),
+                details: Vec::new(),
+                breaking: false,
+            });
+        }
+    }
+    changes
+}
+
+/// Constructors only matter to deployments, not to callers.
+fn compared(item: &AbiItem) -> bool {
+    matches!(
+        item.r#type.as_str(),
+        "function" | "event" | "error" | "fallback" | "receive"
+    )
+}
+
+fn key(item: &AbiItem) -> (String, String) {
+    (item.r#type.clone(), signature(item))
+}
+
+/// The canonical signature, or the kind for fallback and receive.
+fn signature(item: &AbiItem) -> String {
+    item.canonical_signature()
+        .unwrap_or_else(|| format!("{}()", item.r#type))
+}
+
+fn changed(item: &AbiItem) -> Change {
+    Change {
+        change: "changed",
+        kind: item.r#type.clone(),
+        signature: signature(item),
+        details: Vec::new(),
+        breaking: false,
+    }
+}
+
+/// The differences between two versions of an item, or `None` if there are
+/// none.
+fn compare(before: &AbiItem, after: &AbiItem) -> Option<Change> {
+    let mut change = changed(before);
+    let mut breaking = Vec::new();
+    let mut compatible = Vec::new();
+
+    if before.r#type != "event" && before.r#type != "error" {
+        let (old, new) = (effective_mutability(before), effective_mutability(after));
+        if old != new {
+            let detail = format!("mutability {old} → {new}");
+            let tightened = (old == "payable" && new != "payable")
+                || (matches!(old, "view" | "pure") && !matches!(new, "view" | "pure"));
+            if tightened {
+                breaking.push(detail);
+            } else {
+                compatible.push(detail);
+            }
+        }
+    }
+
+    if before.r#type == "function" {
+        let (old, new) = (return_types(before), return_types(after));
+        if old != new {
+            breaking.push(format!("returns {old} → {new}"));
+        }
+    }
+
+    if before.r#type == "event" {
+        if signature(before) == signature(after) {
+            let (old, new) = (indexed_params(before), indexed_params(after));
+            if old != new {
+                breaking.push(format!("indexed {old} → {new}"));
+            }
+        }
+        let (old, new) = (
+            before.anonymous == Some(true),
+            after.anonymous == Some(true),
+        );
+        if old != new {
+            breaking.push(format!("anonymous {old} → {new}"));
+        }
+    }
+
+    let (old, new) = (param_names(before), param_names(after));
+    if old != new && signature(before) == signature(after) {
+        compatible.push(format!("parameter names ({old}) → ({new})"));
+    }
+
+    if breaking.is_empty() && compatible.is_empty() {
+        return None;
+    }
+    change.breaking = !breaking.is_empty();
+    change.details = breaking.into_iter().chain(compatible).collect();
+    Some(change)
+}
+
+fn param_names(item: &AbiItem) -> String {
+    item.inputs
+        .iter()
+        .flatten()
+        .map(|input| input.name.as_deref().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,4 +1,15 @@
-use crate::converter::Converter;
+use crate::abi::AbiItem;
+use crate::codegen::unique;
+use crate::converter::{escape_json_string, Contract, Converter, OutputFormat};
+use crate::dedupe::hoist_common;
+use crate::filter::Filter;
+use crate::html::render_report;
+use crate::json_parser::ParseMode;
+use crate::layout::{sort_items, Layout};
+use crate::natspec::NatSpec;
+use crate::stream::AbiStreamReader;
+use crate::structured::{item_json, render_lines, tagged};
+use crate::ts_import::{extract_exported_abis, is_script_file};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
@@ -7,6 +18,15 @@ pub struct ConvertOptions {
     pub suffix: String,
     pub pretty: bool,
     pub pattern: Option<String>,
+    pub format: OutputFormat,
+    pub mode: ParseMode,
+    pub layout: Layout,
+    pub filter: Filter,
+    /// Write a directory as one document instead of a file per input.
+    pub combine: bool,
+    /// Hoist items shared between contracts into `common` sections of
+    /// combined output.
+    pub dedupe: bool,
 }
 
 impl Default for ConvertOptions {
@@ -15,6 +35,12 @@ impl Default for ConvertOptions {
             suffix: ".readable".to_string(),
             pretty: true,
             pattern: None,
+            format: OutputFormat::default(),
+            mode: ParseMode::default(),
+            layout: Layout::default(),
+            filter: Filter::default(),
+            combine: false,
+            dedupe: false,
         }
     }
 }
@@ -27,6 +53,74 @@ pub struct ConvertResult {
     pub item_count: Option<usize>,
 }
 
+/// Reads and parses the ABI in `path`. For TypeScript/JavaScript modules the
+/// items of every exported ABI are concatenated.
+pub fn load_abi(path: &Path, mode: ParseMode) -> Result<Vec<AbiItem>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+    let items = if is_script_file(path) {
+        extract_exported_abis(&content)?
+            .into_iter()
+            .flat_map(|(_, items)| items)
+            .collect()
+    } else {
+        Converter::parse_abi_content_with_mode(&content, mode)?
+    };
+
+    if items.is_empty() {
+        return Err(format!("No valid ABI items found in {}", path.display()));
+    }
+    Ok(items)
+}
+
+/// Reads the contracts in `path`, with their NatSpec when `format` renders
+/// it: one per JSON file, or one per exported ABI of a TypeScript/JavaScript
+/// module.
+pub fn load_contracts(
+    path: &Path,
+    mode: ParseMode,
+    format: OutputFormat,
+) -> Result<Vec<Contract>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {e}"))?;
+
+    let contracts: Vec<Contract> = if is_script_file(path) {
+        extract_exported_abis(&content)
+            .map_err(|e| format!("Failed to parse ABI: {e}"))?
+            .into_iter()
+            .map(|(name, abi)| Contract {
+                name,
+                abi,
+                docs: NatSpec::default(),
+            })
+            .collect()
+    } else {
+        let abi = Converter::parse_abi_content_with_mode(&content, mode)
+            .map_err(|e| format!("Failed to parse ABI: {e}"))?;
+        vec![Contract {
+            name: contract_name(path),
+            abi,
+            docs: Converter::parse_natspec(&content, mode, format),
+        }]
+    };
+
+    if contracts.iter().all(|c| c.abi.is_empty()) {
+        return Err("No valid ABI items found".to_string());
+    }
+    Ok(contracts)
+}
+
+/// The contract name implied by an artifact path: `out/Token.sol/Token.json`
+/// and `Token.abi.json` both give `Token`.
+pub fn contract_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("Contract")
+        .to_string()
+}
+
 pub fn convert_file(
     input_path: &Path,
     output_path: Option<&Path>,
@@ -45,31 +139,80 @@ pub fn convert_file(
         }
     };
 
-    let abi_items = match Converter::parse_abi_content(&content) {
-        Ok(items) => items,
-        Err(e) => {
+    let (formatted, item_count) = if is_script_file(input_path) {
+        let exports = match extract_exported_abis(&content) {
+            Ok(exports) => exports,
+            Err(e) => {
+                return ConvertResult {
+                    input_path: input_path.to_path_buf(),
+                    output_path: output_path.map(|p| p.to_path_buf()),
+                    success: false,
+                    error: Some(format!("Failed to parse ABI: {e}")),
+                    item_count: None,
+                };
+            }
+        };
+
+        if exports.is_empty() {
             return ConvertResult {
                 input_path: input_path.to_path_buf(),
                 output_path: output_path.map(|p| p.to_path_buf()),
                 success: false,
-                error: Some(format!("Failed to parse ABI: {e}")),
-                item_count: None,
+                error: Some("No exported ABI arrays found".to_string()),
+                item_count: Some(0),
             };
         }
-    };
 
-    if abi_items.is_empty() {
-        return ConvertResult {
-            input_path: input_path.to_path_buf(),
-            output_path: output_path.map(|p| p.to_path_buf()),
-            success: false,
-            error: Some("No valid ABI items found".to_string()),
-            item_count: Some(0),
+        let exports: Vec<(String, Vec<AbiItem>)> = exports
+            .into_iter()
+            .map(|(name, items)| (name, options.filter.apply(&items)))
+            .collect();
+        let count = exports
+            .iter()
+            .map(|(_, items)| Converter::convert_to_human_readable(items).len())
+            .sum();
+        (
+            Converter::format_exports(&exports, options.format, options.pretty, options.layout),
+            count,
+        )
+    } else {
+        let abi_items = match Converter::parse_abi_content_with_mode(&content, options.mode) {
+            Ok(items) => items,
+            Err(e) => {
+                return ConvertResult {
+                    input_path: input_path.to_path_buf(),
+                    output_path: output_path.map(|p| p.to_path_buf()),
+                    success: false,
+                    error: Some(format!("Failed to parse ABI: {e}")),
+                    item_count: None,
+                };
+            }
         };
-    }
 
-    let human_readable = Converter::convert_to_human_readable(&abi_items);
-    let formatted = Converter::format_as_json_array(&human_readable, options.pretty);
+        if abi_items.is_empty() {
+            return ConvertResult {
+                input_path: input_path.to_path_buf(),
+                output_path: output_path.map(|p| p.to_path_buf()),
+                success: false,
+                error: Some("No valid ABI items found".to_string()),
+                item_count: Some(0),
+            };
+        }
+
+        let formatted = Converter::format_contract(
+            &contract_name(input_path),
+            &abi_items,
+            &Converter::parse_natspec(&content, options.mode, options.format),
+            options.format,
+            options.pretty,
+            options.layout,
+            &options.filter,
+        );
+        (
+            formatted,
+            Converter::convert_to_human_readable(&options.filter.apply(&abi_items)).len(),
+        )
+    };
 
     let final_output_path = if let Some(path) = output_path {
         path.to_path_buf()
@@ -79,7 +222,7 @@ pub fn convert_file(
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("output");
-        let new_name = format!("{}{}.json", stem, options.suffix);
+        let new_name = format!("{}{}.{}", stem, options.suffix, options.format.extension());
         path.set_file_name(new_name);
         path
     };
@@ -92,7 +235,7 @@ pub fn convert_file(
                     output_path: Some(final_output_path),
                     success: false,
                     error: Some(format!("Failed to create directory: {e}")),
-                    item_count: Some(human_readable.len()),
+                    item_count: Some(item_count),
                 };
             }
         }
@@ -104,14 +247,14 @@ pub fn convert_file(
             output_path: Some(final_output_path),
             success: true,
             error: None,
-            item_count: Some(human_readable.len()),
+            item_count: Some(item_count),
         },
         Err(e) => ConvertResult {
             input_path: input_path.to_path_buf(),
             output_path: Some(final_output_path),
             success: false,
             error: Some(format!("Failed to write file: {e}")),
-            item_count: Some(human_readable.len()),
+            item_count: Some(item_count),
         },
     }
 }
@@ -121,55 +264,220 @@ pub fn convert_directory(
     output_dir: &Path,
     options: &ConvertOptions,
 ) -> Vec<ConvertResult> {
-    let mut results = Vec::new();
-
-    let entries = match fs::read_dir(input_dir) {
-        Ok(e) => e,
-        Err(e) => {
-            results.push(ConvertResult {
-                input_path: input_dir.to_path_buf(),
-                output_path: None,
-                success: false,
-                error: Some(format!("Failed to read directory: {e}")),
-                item_count: None,
-            });
-            return results;
+    if options.combine || options.format.combined() {
+        let output_path = output_dir.join(format!("index.{}", options.format.extension()));
+        let (document, mut results) = combine_directory(input_dir, output_dir, options);
+        if let Some(document) = document {
+            let written = fs::create_dir_all(output_dir)
+                .and_then(|_| fs::write(&output_path, format!("{document}\n")));
+            for result in results.iter_mut().filter(|r| r.success) {
+                result.output_path = Some(output_path.clone());
+                if let Err(e) = &written {
+                    result.success = false;
+                    result.error = Some(format!("Failed to write file: {e}"));
+                }
+            }
         }
+        return results;
+    }
+
+    let files = match matching_files(input_dir, output_dir, options) {
+        Ok(files) => files,
+        Err(result) => return vec![result],
     };
 
-    for entry in entries.filter_map(Result::ok) {
-        let path = entry.path();
-
-        if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if ext == "json" {
-                    if let Some(pattern) = &options.pattern {
-                        if let Some(file_name) = path.file_name() {
-                            if let Some(name_str) = file_name.to_str() {
-                                if !matches_pattern(name_str, pattern) {
-                                    continue;
-                                }
+    files
+        .into_iter()
+        .map(|path| {
+            let relative = path.strip_prefix(input_dir).unwrap_or(&path);
+            let output_path = output_dir
+                .join(relative)
+                .with_extension(options.format.extension());
+            convert_file(&path, Some(&output_path), options)
+        })
+        .collect()
+}
+
+/// The ABI files under `input_dir` that `options.pattern` selects, sorted by
+/// path. Subdirectories are searched too, so a Foundry `out/Token.sol/`
+/// tree is picked up, except `build-info/` and `cache/`, whose JSON files
+/// are compiler bookkeeping rather than ABIs. Files under `output_dir` are
+/// left out as well: the default output directory sits inside the input
+/// one, and earlier output must not be read back as input. Finding no files
+/// is an error.
+fn matching_files(
+    input_dir: &Path,
+    output_dir: &Path,
+    options: &ConvertOptions,
+) -> Result<Vec<PathBuf>, ConvertResult> {
+    let failure = |error: String| ConvertResult {
+        input_path: input_dir.to_path_buf(),
+        output_path: None,
+        success: false,
+        error: Some(error),
+        item_count: None,
+    };
+
+    let mut files = Vec::new();
+    collect_files(input_dir, options, &mut files)
+        .map_err(|e| failure(format!("Failed to read directory: {e}")))?;
+    files.retain(|path| output_dir == input_dir || !path.starts_with(output_dir));
+    if files.is_empty() {
+        return Err(failure("No ABI files found".to_string()));
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(dir: &Path, options: &ConvertOptions, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            let skipped = path
+                .file_name()
+                .is_some_and(|name| name == "build-info" || name == "cache");
+            if !skipped {
+                collect_files(&path, options, files)?;
+            }
+        } else if let Some(ext) = path.extension() {
+            // Script modules are only picked up when a pattern asks for
+            // them, so frontend source trees don't produce failures.
+            let script = is_script_file(&path) && options.pattern.is_some();
+            if ext == "json" || script {
+                if let Some(pattern) = &options.pattern {
+                    if let Some(file_name) = path.file_name() {
+                        if let Some(name_str) = file_name.to_str() {
+                            if !matches_pattern(name_str, pattern) {
+                                continue;
                             }
                         }
                     }
+                }
+                files.push(path);
+            }
+        }
+    }
+    Ok(())
+}
 
-                    let relative = path.strip_prefix(input_dir).unwrap_or(&path);
-                    let output_path = output_dir.join(relative);
+/// Renders every contract in `input_dir` as a single document: a JSON
+/// object keyed by contract name, `// Name` sections of raw text, one HTML
+/// report and so on. Files that fail to load are reported and left out;
+/// the document is `None` when nothing loaded. Files under `output_dir`,
+/// where the document is written, are not read.
+pub fn combine_directory(
+    input_dir: &Path,
+    output_dir: &Path,
+    options: &ConvertOptions,
+) -> (Option<String>, Vec<ConvertResult>) {
+    let files = match matching_files(input_dir, output_dir, options) {
+        Ok(files) => files,
+        Err(result) => return (None, vec![result]),
+    };
 
-                    results.push(convert_file(&path, Some(&output_path), options));
-                }
+    let mut results = Vec::new();
+    let mut contracts = Vec::new();
+    for path in files {
+        match load_contracts(&path, options.mode, options.format) {
+            Ok(loaded) => {
+                let loaded: Vec<Contract> = loaded
+                    .into_iter()
+                    .map(|contract| Contract {
+                        abi: sort_items(&options.filter.apply(&contract.abi), options.layout.sort),
+                        ..contract
+                    })
+                    .collect();
+                results.push(ConvertResult {
+                    input_path: path.clone(),
+                    output_path: None,
+                    success: true,
+                    error: None,
+                    item_count: Some(
+                        loaded
+                            .iter()
+                            .map(|c| Converter::convert_to_human_readable(&c.abi).len())
+                            .sum(),
+                    ),
+                });
+                contracts.extend(loaded.into_iter().map(|contract| (path.clone(), contract)));
             }
+            Err(e) => results.push(ConvertResult {
+                input_path: path,
+                output_path: None,
+                success: false,
+                error: Some(e),
+                item_count: None,
+            }),
         }
     }
 
-    results
+    if contracts.is_empty() {
+        return (None, results);
+    }
+
+    let document = match options.format {
+        OutputFormat::Html => {
+            let mut contracts: Vec<Contract> = contracts
+                .into_iter()
+                .map(|(_, contract)| contract)
+                .collect();
+            contracts.sort_by(|a, b| a.name.cmp(&b.name));
+            // Section and row ids are built from the name, so artifacts
+            // that share one, such as `A.sol/Token.json` and
+            // `B.sol/Token.json`, need distinct names to keep links apart.
+            let mut taken = Vec::new();
+            for contract in &mut contracts {
+                contract.name = unique(&mut taken, std::mem::take(&mut contract.name));
+            }
+            let title = input_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("ABI Report");
+            render_report(title, &contracts)
+        }
+        OutputFormat::Ndjson => contracts
+            .iter()
+            .map(|(path, contract)| {
+                render_lines(
+                    Some(&path.display().to_string()),
+                    &contract.name,
+                    &contract.abi,
+                )
+            })
+            .filter(|lines| !lines.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        format => {
+            // Artifacts such as `Token.json` and `Token.abi.json` share a
+            // contract name; later ones get a numbered key.
+            let mut taken = Vec::new();
+            let sections: Vec<(String, Vec<AbiItem>)> = contracts
+                .into_iter()
+                .map(|(_, contract)| (unique(&mut taken, contract.name), contract.abi))
+                .collect();
+            if options.dedupe {
+                Converter::format_hoisted(
+                    &hoist_common(&sections),
+                    format,
+                    options.pretty,
+                    options.layout,
+                )
+            } else {
+                Converter::format_exports(&sections, format, options.pretty, options.layout)
+            }
+        }
+    };
+    (Some(document), results)
 }
 
-pub fn convert_stdin_to_stdout(options: &ConvertOptions) -> io::Result<()> {
+/// Converts the ABI on stdin, returning the input and output sizes in
+/// bytes.
+pub fn convert_stdin_to_stdout(options: &ConvertOptions) -> io::Result<(usize, usize)> {
     let mut content = String::new();
     io::stdin().read_to_string(&mut content)?;
 
-    let abi_items = Converter::parse_abi_content(&content)
+    let abi_items = Converter::parse_abi_content_with_mode(&content, options.mode)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
     if abi_items.is_empty() {
@@ -179,13 +487,117 @@ pub fn convert_stdin_to_stdout(options: &ConvertOptions) -> io::Result<()> {
         ));
     }
 
-    let human_readable = Converter::convert_to_human_readable(&abi_items);
-    let formatted = Converter::format_as_json_array(&human_readable, options.pretty);
+    let formatted = Converter::format_contract(
+        "Contract",
+        &abi_items,
+        &Converter::parse_natspec(&content, options.mode, options.format),
+        options.format,
+        options.pretty,
+        options.layout,
+        &options.filter,
+    );
 
     io::stdout().write_all(formatted.as_bytes())?;
     io::stdout().write_all(b"\n")?;
 
-    Ok(())
+    Ok((content.len(), formatted.len()))
+}
+
+/// Converts ABI items as they are read from `reader`, writing each readable
+/// line to `writer` immediately. Memory use is bounded by the largest single
+/// ABI item rather than the input size, which matters for solc standard-JSON
+/// output and build-info files.
+pub fn convert_stream<R: Read, W: Write>(
+    reader: R,
+    mut writer: W,
+    options: &ConvertOptions,
+) -> io::Result<usize> {
+    let format = options.format;
+    if !matches!(
+        format,
+        OutputFormat::Json | OutputFormat::Structured | OutputFormat::Ndjson | OutputFormat::Raw
+    ) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Streaming supports only json, structured, ndjson and raw output",
+        ));
+    }
+    let array = matches!(format, OutputFormat::Json | OutputFormat::Structured);
+
+    let mut stream = AbiStreamReader::with_mode(reader, options.mode);
+    let mut written = 0;
+    let mut current_contract: Option<String> = None;
+    let mut write_error: Option<io::Error> = None;
+
+    let result = stream.for_each_item(|path, item| {
+        if write_error.is_some() || !options.filter.matches(&item) {
+            return;
+        }
+
+        for line in Converter::convert_to_human_readable(std::slice::from_ref(&item)) {
+            let chunk = if format == OutputFormat::Ndjson {
+                let contract = path.last().map(String::as_str).unwrap_or("Contract");
+                format!(
+                    "{}\n",
+                    tagged(None, contract, item_json(&item)).render(false)
+                )
+            } else if format == OutputFormat::Raw {
+                let mut chunk = String::new();
+                if let Some(contract) = path.last() {
+                    if current_contract.as_ref() != Some(contract) {
+                        chunk.push_str(&format!("// {contract}\n"));
+                        current_contract = Some(contract.clone());
+                    }
+                }
+                chunk.push_str(&line);
+                chunk.push('\n');
+                chunk
+            } else {
+                // The array opens with its first item, so input that turns
+                // out to hold no ABI leaves no partial document behind.
+                let separator = match (written > 0, options.pretty) {
+                    (true, true) => ",\n  ",
+                    (true, false) => ",",
+                    (false, true) => "[\n  ",
+                    (false, false) => "[",
+                };
+                if format == OutputFormat::Structured {
+                    let object = item_json(&item).render(options.pretty);
+                    format!("{separator}{}", object.replace('\n', "\n  "))
+                } else {
+                    format!("{separator}\"{}\"", escape_json_string(&line))
+                }
+            };
+
+            if let Err(e) = writer.write_all(chunk.as_bytes()) {
+                write_error = Some(e);
+                return;
+            }
+            written += 1;
+        }
+    });
+
+    if let Some(e) = write_error {
+        return Err(e);
+    }
+    result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if written == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "No valid ABI items found",
+        ));
+    }
+
+    if array {
+        if options.pretty {
+            writer.write_all(b"\n")?;
+        }
+        writer.write_all(b"]\n")?;
+    }
+    writer.flush()?;
+
+    Ok(written)
 }
 
 fn matches_pattern(filename: &str, pattern: &str) -> bool {
@@ -0,0 +1,166 @@
+use crate::abi::AbiItem;
+use crate::abi_codec::to_hex;
+use crate::canonical::effective_mutability;
+
+/// Selects ABI items by kind, mutability, name and selector. Each criterion
+/// is a list of values; a value prefixed with `!` excludes instead of
+/// includes. An item is kept when it passes every criterion.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub kinds: Vec<String>,
+    pub mutability: Vec<String>,
+    pub names: Vec<String>,
+    pub selectors: Vec<String>,
+}
+
+impl Filter {
+    /// Adds the comma-separated kinds in `list`, such as `functions,events`.
+    pub fn add_kinds(&mut self, list: &str) -> Result<(), String> {
+        for value in split(list) {
+            let (negated, kind) = negation(&value);
+            let kind = match kind.to_ascii_lowercase().as_str() {
+                "function" | "functions" => "function",
+                "event" | "events" => "event",
+                "error" | "errors" => "error",
+                "constructor" => "constructor",
+                "fallback" => "fallback",
+                "receive" => "receive",
+                _ => {
+                    return Err(format!(
+                        "Unknown item kind '{kind}' (expected functions, events, errors, \
+                         constructor, fallback or receive)"
+                    ))
+                }
+            };
+            self.kinds.push(format!("{negated}{kind}"));
+        }
+        Ok(())
+    }
+
+    /// Adds the comma-separated state mutabilities in `list`.
+    pub fn add_mutability(&mut self, list: &str) -> Result<(), String> {
+        for value in split(list) {
+            let (negated, mutability) = negation(&value);
+            let mutability = mutability.to_ascii_lowercase();
+            if !["view", "pure", "nonpayable", "payable"].contains(&mutability.as_str()) {
+                return Err(format!(
+                    "Unknown state mutability '{mutability}' (expected view, pure, nonpayable \
+                     or payable)"
+                ));
+            }
+            self.mutability.push(format!("{negated}{mutability}"));
+        }
+        Ok(())
+    }
+
+    /// Adds the comma-separated name patterns in `list`; `*` matches any
+    /// run of characters and `?` a single one.
+    pub fn add_names(&mut self, list: &str) {
+        self.names.extend(split(list));
+    }
+
+    /// Adds the comma-separated selectors or event topics in `list`. A
+    /// value may be any hex prefix of the selector or topic.
+    pub fn add_selectors(&mut self, list: &str) -> Result<(), String> {
+        for value in split(list) {
+            let (negated, hex) = negation(&value);
+            let hex = hex.trim_start_matches("0x").to_ascii_lowercase();
+            if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("Invalid selector '{value}'"));
+            }
+            self.selectors.push(format!("{negated}0x{hex}"));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, item: &AbiItem) -> bool {
+        let mutability = match item.r#type.as_str() {
+            "event" | "error" => None,
+            _ => Some(effective_mutability(item)),
+        };
+        let hashes: Vec<String> = [
+            item.selector().map(|s| to_hex(&s)),
+            item.topic().map(|t| to_hex(&t)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        passes(&self.kinds, |kind| item.r#type == kind)
+            && passes(&self.mutability, |m| mutability == Some(m))
+            && passes(&self.names, |pattern| {
+                item.name.as_deref().is_some_and(|name| glob(pattern, name))
+            })
+            && passes(&self.selectors, |prefix| {
+                hashes.iter().any(|hash| hash.starts_with(prefix))
+            })
+    }
+
+    /// The items of `abi` that match, in their original order.
+    pub fn apply(&self, abi: &[AbiItem]) -> Vec<AbiItem> {
+        abi.iter()
+            .filter(|item| self.matches(item))
+            .cloned()
+            .collect()
+    }
+}
+
+fn split(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn negation(value: &str) -> (&'static str, &str) {
+    match value.strip_prefix('!') {
+        Some(rest) => ("!", rest),
+        None => ("", value),
+    }
+}
+
+/// True when no exclusion matches and, if there are any inclusions, at
+/// least one of them does.
+fn passes(values: &[String], matches: impl Fn(&str) -> bool) -> bool {
+    let mut included = None;
+    for value in values {
+        match value.strip_prefix('!') {
+            Some(excluded) if matches(excluded) => return false,
+            Some(_) => {}
+            None => included = Some(included.unwrap_or(false) || matches(value)),
+        }
+    }
+    included.unwrap_or(true)
+}
+
+/// Matches `text` against a pattern where `*` is any run of characters and
+/// `?` any single character.
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
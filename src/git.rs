@@ -0,0 +1,124 @@
+use crate::abi::AbiItem;
+use crate::converter::Converter;
+use crate::diff::{diff_abis, Change};
+use crate::json_parser::ParseMode;
+use std::path::Path;
+use std::process::Command;
+
+/// Splits a `from..to` revision range. Either side may be left out and
+/// defaults to `HEAD`, as with `git diff`.
+pub fn split_range(range: &str) -> Result<(String, String), String> {
+    let invalid = || format!("Invalid revision range '{range}', expected <from>..<to>");
+    if range.contains("...") {
+        return Err(invalid());
+    }
+    let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+    // Revisions reach git as arguments; one that looks like an option is
+    // never a valid revision.
+    if from.starts_with('-') || to.starts_with('-') {
+        return Err(invalid());
+    }
+    let side = |rev: &str| {
+        if rev.is_empty() {
+            "HEAD".to_string()
+        } else {
+            rev.to_string()
+        }
+    };
+    Ok((side(from), side(to)))
+}
+
+/// Diffs the ABI of every `.json` artifact under `path` between the two
+/// revisions of `range`, running the local `git` binary in `dir`.
+///
+/// Returns each artifact whose ABI changed, by path from the repository
+/// root, with its changes. An artifact present in only one revision shows
+/// all of its items as added or removed. Files that hold no ABI in either
+/// revision, such as build info, are skipped; an artifact that parses in
+/// one revision but not in the other is an error.
+pub fn diff_revisions(
+    dir: &Path,
+    range: &str,
+    path: &str,
+    mode: ParseMode,
+) -> Result<Vec<(String, Vec<Change>)>, String> {
+    let (from, to) = split_range(range)?;
+    let mut files = list_artifacts(dir, &from, path)?;
+    for file in list_artifacts(dir, &to, path)? {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    files.sort();
+
+    let mut results = Vec::new();
+    for file in files {
+        let old = read_abi(dir, &from, &file, mode)?;
+        let new = read_abi(dir, &to, &file, mode)?;
+        let (old, new) = match (old, new) {
+            (None | Some(Err(_)), None | Some(Err(_))) => continue,
+            (Some(Err(e)), _) => return Err(format!("Failed to parse {file} at {from}: {e}")),
+            (_, Some(Err(e))) => return Err(format!("Failed to parse {file} at {to}: {e}")),
+            (old, new) => (
+                old.and_then(Result::ok).unwrap_or_default(),
+                new.and_then(Result::ok).unwrap_or_default(),
+            ),
+        };
+        let changes = diff_abis(&old, &new);
+        if !changes.is_empty() {
+            results.push((file, changes));
+        }
+    }
+    Ok(results)
+}
+
+/// The `.json` files under `path` at `rev`, relative to the repository root.
+fn list_artifacts(dir: &Path, rev: &str, path: &str) -> Result<Vec<String>, String> {
+    let listing = git(
+        dir,
+        &[
+            "ls-tree",
+            "-r",
+            "--name-only",
+            "--full-name",
+            "--end-of-options",
+            rev,
+            "--",
+            path,
+        ],
+    )?;
+    Ok(listing
+        .lines()
+        .filter(|file| file.ends_with(".json"))
+        .map(str::to_string)
+        .collect())
+}
+
+/// The parsed ABI in `file` at `rev`, or `None` when the file does not
+/// exist there.
+fn read_abi(
+    dir: &Path,
+    rev: &str,
+    file: &str,
+    mode: ParseMode,
+) -> Result<Option<Result<Vec<AbiItem>, String>>, String> {
+    let object = format!("{rev}:{file}");
+    if git(dir, &["cat-file", "-e", "--end-of-options", &object]).is_err() {
+        return Ok(None);
+    }
+    let content = git(dir, &["show", "--end-of-options", &object])?;
+    Ok(Some(Converter::parse_abi_content_with_mode(&content, mode)))
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("git {} output: {e}", args[0]))
+}
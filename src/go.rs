@@ -0,0 +1,161 @@
+use crate::abi::AbiItem;
+use crate::abi_codec::to_hex;
+use crate::codegen::{param_name, unique, upper_camel, Param, Structs, TypeShape};
+
+/// Renders named ABIs as a Go source file: selector and topic constants and
+/// a struct per tuple type, with `abi` tags matching go-ethereum's. The
+/// package is named after the first contract, and with several ABIs the
+/// constants are prefixed with the contract name.
+pub fn render_go(contracts: &[(String, Vec<AbiItem>)]) -> String {
+    let prefixed = contracts.len() > 1;
+    let mut structs = Structs::default();
+    let mut constants = Vec::new();
+    let mut taken = Vec::new();
+
+    for (name, abi) in contracts {
+        let prefix = if prefixed {
+            upper_camel(name)
+        } else {
+            String::new()
+        };
+
+        for item in abi {
+            for param in item.inputs.iter().flatten().map(Param::from) {
+                structs.shape(&param);
+            }
+            for param in item.outputs.iter().flatten().map(Param::from) {
+                structs.shape(&param);
+            }
+
+            let Some(signature) = item.canonical_signature() else {
+                continue;
+            };
+            let base = upper_camel(item.name.as_deref().unwrap_or(""));
+            let (suffix, hash) = match item.r#type.as_str() {
+                "function" => ("Selector", item.selector().map(|s| to_hex(&s))),
+                "event" => ("Topic", item.topic().map(|t| to_hex(&t))),
+                "error" => ("ErrorSelector", item.selector().map(|s| to_hex(&s))),
+                _ => continue,
+            };
+            if let Some(hash) = hash {
+                let ident = unique(&mut taken, format!("{prefix}{base}{suffix}"));
+                constants.push((signature, ident, hash));
+            }
+        }
+    }
+
+    let package: String = contracts
+        .first()
+        .map(|(name, _)| name.as_str())
+        .unwrap_or("contract")
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let package = if package.is_empty() || package.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("contract{package}")
+    } else {
+        package
+    };
+
+    let struct_blocks: Vec<String> = (0..structs.defs.len())
+        .map(|index| {
+            let fields = structs.defs[index].fields.clone();
+            let rows: Vec<[String; 3]> = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let tag = param_name(field, "field", i);
+                    [
+                        upper_camel(&tag),
+                        go_type(&structs.shape(field)),
+                        format!("`abi:\"{tag}\"`"),
+                    ]
+                })
+                .collect();
+            let name = &structs.defs[index].name;
+            format!(
+                "// {name} mirrors the Solidity struct {name}.\ntype {name} struct {{\n{}}}",
+                aligned(&rows, "\t")
+            )
+        })
+        .collect();
+
+    let mut out = format!("// Code generated by abi2human. DO NOT EDIT.\n\npackage {package}\n");
+    if struct_blocks.iter().any(|block| block.contains("*big.Int")) {
+        out.push_str("\nimport \"math/big\"\n");
+    }
+
+    if !constants.is_empty() {
+        let rows: Vec<[String; 3]> = constants
+            .into_iter()
+            .map(|(signature, ident, hash)| {
+                [ident, format!("= \"{hash}\""), format!("// {signature}")]
+            })
+            .collect();
+        out.push_str(&format!(
+            "\n// Function selectors, event topics and error selectors.\nconst (\n{})\n",
+            aligned(&rows, "\t")
+        ));
+    }
+
+    for block in struct_blocks {
+        out.push('\n');
+        out.push_str(&block);
+        out.push('\n');
+    }
+
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Lays out rows in space-padded columns the way gofmt aligns them.
+fn aligned(rows: &[[String; 3]], indent: &str) -> String {
+    let widths: Vec<usize> = (0..2)
+        .map(|col| rows.iter().map(|row| row[col].len()).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|[a, b, c]| {
+            format!(
+                "{indent}{a:<w0$} {b:<w1$} {c}\n",
+                w0 = widths[0],
+                w1 = widths[1]
+            )
+        })
+        .collect()
+}
+
+fn go_type(shape: &TypeShape) -> String {
+    match shape {
+        TypeShape::Struct(name) => name.clone(),
+        TypeShape::Array(inner, Some(len)) => format!("[{len}]{}", go_type(inner)),
+        TypeShape::Array(inner, None) => format!("[]{}", go_type(inner)),
+        TypeShape::Elementary(name) => match name.as_str() {
+            "address" => "[20]byte".to_string(),
+            "bool" => "bool".to_string(),
+            "string" => "string".to_string(),
+            "bytes" => "[]byte".to_string(),
+            "function" => "[24]byte".to_string(),
+            other => {
+                if let Some(bits) = other.strip_prefix("uint") {
+                    integer_type("uint", bits)
+                } else if let Some(bits) = other.strip_prefix("int") {
+                    integer_type("int", bits)
+                } else if let Some(len) = other.strip_prefix("bytes") {
+                    format!("[{len}]byte")
+                } else {
+                    "[32]byte".to_string()
+                }
+            }
+        },
+    }
+}
+
+/// Go's fixed-size integers for exactly 8, 16, 32 and 64 bits, `*big.Int`
+/// for every other size, as go-ethereum decodes them.
+fn integer_type(base: &str, bits: &str) -> String {
+    match bits.parse::<usize>() {
+        Ok(size @ (8 | 16 | 32 | 64)) => format!("{base}{size}"),
+        _ => "*big.Int".to_string(),
+    }
+}
@@ -0,0 +1,128 @@
+use crate::abi::AbiItem;
+use crate::abi_codec::to_hex;
+use crate::canonical::kind_rank;
+use crate::converter::Contract;
+
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;max-width:72rem;margin:2rem auto;padding:0 1rem;color:#222}
+code{font-family:ui-monospace,monospace;font-size:.9em}
+nav ul{columns:3;list-style:none;padding:0}
+details{margin:.5rem 0}
+details>summary{cursor:pointer}
+section>details>summary{font-size:1.4rem;font-weight:600}
+.kind{margin-left:1.5rem}
+.kind>summary{font-weight:600}
+table{border-collapse:collapse;width:100%}
+td{border-top:1px solid #ddd;padding:.3rem .5rem;vertical-align:top}
+td.selector{white-space:nowrap;width:1%}
+td.selector a{color:#666;text-decoration:none}
+tr:target{background:#fff6d0}
+p.notice{margin:.2rem 0 0;color:#555}";
+
+/// Item groups within a contract, in `kind_rank` order.
+const GROUPS: [(&str, &[&str]); 4] = [
+    ("Constructor", &["constructor"]),
+    ("Functions", &["fallback", "receive", "function"]),
+    ("Events", &["event"]),
+    ("Errors", &["error"]),
+];
+
+/// Renders contracts as one self-contained HTML page: inline CSS, no
+/// scripts, collapsible per contract and per item kind, and an anchor on
+/// every item.
+pub fn render_report(title: &str, contracts: &[Contract]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<nav>\n<ul>\n",
+        title = escape(title)
+    );
+    for contract in contracts {
+        out.push_str(&format!(
+            "<li><a href=\"#{id}\">{name}</a></li>\n",
+            id = escape(&contract.name),
+            name = escape(&contract.name)
+        ));
+    }
+    out.push_str("</ul>\n</nav>\n");
+
+    for contract in contracts {
+        render_contract(&mut out, contract);
+    }
+
+    out.push_str("</body>\n</html>");
+    out
+}
+
+fn render_contract(out: &mut String, contract: &Contract) {
+    let name = escape(&contract.name);
+    out.push_str(&format!(
+        "<section id=\"{name}\">\n<details open>\n<summary>{name}</summary>\n"
+    ));
+    for text in [&contract.docs.title, &contract.docs.notice]
+        .into_iter()
+        .flatten()
+    {
+        out.push_str(&format!("<p class=\"notice\">{}</p>\n", escape(text)));
+    }
+
+    for (heading, kinds) in GROUPS {
+        let mut items: Vec<&AbiItem> = contract
+            .abi
+            .iter()
+            .filter(|item| kinds.contains(&item.r#type.as_str()))
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        items.sort_by_key(|item| kind_rank(&item.r#type));
+
+        out.push_str(&format!(
+            "<details class=\"kind\" open>\n<summary>{heading} ({})</summary>\n<table>\n",
+            items.len()
+        ));
+        for item in items {
+            render_item(out, contract, item);
+        }
+        out.push_str("</table>\n</details>\n");
+    }
+
+    out.push_str("</details>\n</section>\n");
+}
+
+fn render_item(out: &mut String, contract: &Contract, item: &AbiItem) {
+    let signature = item
+        .canonical_signature()
+        .unwrap_or_else(|| format!("{}()", item.r#type));
+    let id = escape(&format!("{}-{signature}", contract.name));
+    let selector = item
+        .selector()
+        .map(|s| to_hex(&s))
+        .or_else(|| item.topic().map(|t| to_hex(&t)))
+        .unwrap_or_default();
+
+    out.push_str(&format!(
+        "<tr id=\"{id}\"><td class=\"selector\"><a href=\"#{id}\"><code>{selector}</code></a></td>\
+         <td><code>{}</code>",
+        escape(&item.to_string())
+    ));
+    if let Some(notice) = contract.docs.for_item(item).and_then(|d| d.notice.as_ref()) {
+        out.push_str(&format!("<p class=\"notice\">{}</p>", escape(notice)));
+    }
+    out.push_str("</td></tr>\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,227 @@
+use crate::abi::AbiItem;
+use crate::conformance::indexed_params;
+
+/// A standard interface, by the canonical signatures of its members.
+/// Events are written with `indexed` markers so that ERC-20's `Transfer`
+/// is not mistaken for ERC-721's, which has the same canonical signature.
+pub struct Standard {
+    pub name: &'static str,
+    /// Only reported when this base standard is at least partially
+    /// implemented, e.g. metadata extensions.
+    pub extends: Option<&'static str>,
+    pub functions: &'static [&'static str],
+    pub events: &'static [&'static str],
+}
+
+pub const STANDARDS: &[Standard] = &[
+    Standard {
+        name: "ERC-20",
+        extends: None,
+        functions: &[
+            "totalSupply()",
+            "balanceOf(address)",
+            "transfer(address,uint256)",
+            "transferFrom(address,address,uint256)",
+            "approve(address,uint256)",
+            "allowance(address,address)",
+        ],
+        events: &[
+            "Transfer(address indexed,address indexed,uint256)",
+            "Approval(address indexed,address indexed,uint256)",
+        ],
+    },
+    Standard {
+        name: "ERC-165",
+        extends: None,
+        functions: &["supportsInterface(bytes4)"],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-173",
+        extends: None,
+        functions: &["owner()", "transferOwnership(address)"],
+        events: &["OwnershipTransferred(address indexed,address indexed)"],
+    },
+    Standard {
+        name: "ERC-721",
+        extends: None,
+        functions: &[
+            "balanceOf(address)",
+            "ownerOf(uint256)",
+            "safeTransferFrom(address,address,uint256,bytes)",
+            "safeTransferFrom(address,address,uint256)",
+            "transferFrom(address,address,uint256)",
+            "approve(address,uint256)",
+            "setApprovalForAll(address,bool)",
+            "getApproved(uint256)",
+            "isApprovedForAll(address,address)",
+        ],
+        events: &[
+            "Transfer(address indexed,address indexed,uint256 indexed)",
+            "Approval(address indexed,address indexed,uint256 indexed)",
+            "ApprovalForAll(address indexed,address indexed,bool)",
+        ],
+    },
+    Standard {
+        name: "ERC-721 Metadata",
+        extends: Some("ERC-721"),
+        functions: &["name()", "symbol()", "tokenURI(uint256)"],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-721 Enumerable",
+        extends: Some("ERC-721"),
+        functions: &[
+            "totalSupply()",
+            "tokenOfOwnerByIndex(address,uint256)",
+            "tokenByIndex(uint256)",
+        ],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-1155",
+        extends: None,
+        functions: &[
+            "safeTransferFrom(address,address,uint256,uint256,bytes)",
+            "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+            "balanceOf(address,uint256)",
+            "balanceOfBatch(address[],uint256[])",
+            "setApprovalForAll(address,bool)",
+            "isApprovedForAll(address,address)",
+        ],
+        events: &[
+            "TransferSingle(address indexed,address indexed,address indexed,uint256,uint256)",
+            "TransferBatch(address indexed,address indexed,address indexed,uint256[],uint256[])",
+            "ApprovalForAll(address indexed,address indexed,bool)",
+            "URI(string,uint256 indexed)",
+        ],
+    },
+    Standard {
+        name: "ERC-1271",
+        extends: None,
+        functions: &["isValidSignature(bytes32,bytes)"],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-2612",
+        extends: None,
+        functions: &[
+            "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+            "nonces(address)",
+            "DOMAIN_SEPARATOR()",
+        ],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-2981",
+        extends: None,
+        functions: &["royaltyInfo(uint256,uint256)"],
+        events: &[],
+    },
+    Standard {
+        name: "ERC-4626",
+        extends: Some("ERC-20"),
+        functions: &[
+            "asset()",
+            "totalAssets()",
+            "convertToShares(uint256)",
+            "convertToAssets(uint256)",
+            "maxDeposit(address)",
+            "previewDeposit(uint256)",
+            "deposit(uint256,address)",
+            "maxMint(address)",
+            "previewMint(uint256)",
+            "mint(uint256,address)",
+            "maxWithdraw(address)",
+            "previewWithdraw(uint256)",
+            "withdraw(uint256,address,address)",
+            "maxRedeem(address)",
+            "previewRedeem(uint256)",
+            "redeem(uint256,address,address)",
+        ],
+        events: &[
+            "Deposit(address indexed,address indexed,uint256,uint256)",
+            "Withdraw(address indexed,address indexed,address indexed,uint256,uint256)",
+        ],
+    },
+];
+
+/// How much of a standard an ABI implements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Support {
+    Full,
+    /// More than half of the members are present; lists the missing ones.
+    Partial(Vec<String>),
+    Absent,
+}
+
+/// Checks `abi` against every built-in standard, in `STANDARDS` order.
+pub fn detect(abi: &[AbiItem]) -> Vec<(&'static Standard, Support)> {
+    let functions: Vec<String> = abi
+        .iter()
+        .filter(|item| item.r#type == "function")
+        .filter_map(AbiItem::canonical_signature)
+        .collect();
+    let events: Vec<String> = abi
+        .iter()
+        .filter(|item| item.r#type == "event")
+        .filter_map(event_signature)
+        .collect();
+
+    let mut results: Vec<(&'static Standard, Support)> = Vec::new();
+    for standard in STANDARDS {
+        let missing: Vec<String> = standard
+            .functions
+            .iter()
+            .filter(|f| !functions.iter().any(|present| present == *f))
+            .chain(
+                standard
+                    .events
+                    .iter()
+                    .filter(|e| !events.iter().any(|present| present == *e)),
+            )
+            .map(|member| member.to_string())
+            .collect();
+        let total = standard.functions.len() + standard.events.len();
+        let base_present = standard.extends.is_none_or(|base| {
+            results
+                .iter()
+                .any(|(s, support)| s.name == base && *support != Support::Absent)
+        });
+
+        let support = if !base_present {
+            Support::Absent
+        } else if missing.is_empty() {
+            Support::Full
+        } else if (total - missing.len()) * 2 > total {
+            Support::Partial(missing)
+        } else {
+            Support::Absent
+        };
+        results.push((standard, support));
+    }
+    results
+}
+
+/// A one-line summary such as `ERC-20, ERC-165, ERC-2612 (partial, missing
+/// nonces(address))`, or `None` when no standard is even partially present.
+pub fn summary(abi: &[AbiItem]) -> Option<String> {
+    let found: Vec<String> = detect(abi)
+        .into_iter()
+        .filter_map(|(standard, support)| match support {
+            Support::Full => Some(standard.name.to_string()),
+            Support::Partial(missing) => Some(format!(
+                "{} (partial, missing {})",
+                standard.name,
+                missing.join(", ")
+            )),
+            Support::Absent => None,
+        })
+        .collect();
+    (!found.is_empty()).then(|| found.join(", "))
+}
+
+/// The canonical signature of an event with `indexed` markers.
+fn event_signature(item: &AbiItem) -> Option<String> {
+    Some(format!("{}{}", item.name.as_deref()?, indexed_params(item)))
+}
@@ -1,33 +1,137 @@
 use crate::abi::{AbiInput, AbiItem, AbiOutput};
+use crate::natspec::{doc_key, Doc, NatSpec};
 use std::collections::HashMap;
 
+/// How closely input must follow RFC 8259.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Accepts the sloppy JSON found in hand-written ABIs: loose number
+    /// syntax, Unicode whitespace, trailing content and duplicate keys.
+    #[default]
+    Standard,
+    /// Rejects anything RFC 8259 does not allow.
+    Strict,
+    /// Extends `Standard` with the JSON5-style syntax found in ABIs pasted
+    /// from docs and TypeScript: `//` and `/* */` comments, trailing commas,
+    /// single-quoted strings and unquoted keys.
+    Json5,
+}
+
 pub struct JsonParser {
     input: Vec<char>,
     position: usize,
+    mode: ParseMode,
 }
 
 impl JsonParser {
     pub fn new(input: &str) -> Self {
+        Self::with_mode(input, ParseMode::default())
+    }
+
+    pub fn with_mode(input: &str, mode: ParseMode) -> Self {
         Self {
             input: input.chars().collect(),
             position: 0,
+            mode,
         }
     }
 
     pub fn parse_abi(&mut self) -> Result<Vec<AbiItem>, String> {
-        self.skip_whitespace();
+        self.skip_whitespace()?;
 
-        if self.current() == Some('[') {
-            self.parse_abi_array()
+        let items = if self.current() == Some('[') {
+            self.parse_abi_array()?
         } else if self.current() == Some('{') {
             let obj = self.parse_object()?;
             if let Some(Value::Array(arr)) = obj.get("abi") {
-                self.convert_to_abi_items(arr)
+                self.convert_to_abi_items(arr)?
             } else {
-                Err("Expected 'abi' field in object".to_string())
+                return Err("Expected 'abi' field in object".to_string());
             }
         } else {
-            Err("Expected JSON array or object".to_string())
+            return Err("Expected JSON array or object".to_string());
+        };
+
+        self.expect_end()?;
+        Ok(items)
+    }
+
+    /// Extracts the `userdoc`/`devdoc` NatSpec of a compiler artifact, read
+    /// from the top level or from `metadata.output`. Plain ABI arrays have
+    /// none and yield an empty `NatSpec`.
+    pub fn parse_natspec(&mut self) -> Result<NatSpec, String> {
+        let mut natspec = NatSpec::default();
+        self.skip_whitespace()?;
+        if self.current() != Some('{') {
+            return Ok(natspec);
+        }
+
+        let obj = self.parse_object()?;
+        let metadata_output = match obj.get("metadata") {
+            Some(Value::Object(metadata)) => match metadata.get("output") {
+                Some(Value::Object(output)) => Some(output),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        for source in [Some(&obj), metadata_output].into_iter().flatten() {
+            for key in ["userdoc", "devdoc"] {
+                if let Some(Value::Object(doc)) = source.get(key) {
+                    collect_natspec(doc, &mut natspec);
+                }
+            }
+        }
+        Ok(natspec)
+    }
+
+    /// In strict mode, fails if anything other than whitespace follows the
+    /// value that was just parsed.
+    pub fn expect_end(&mut self) -> Result<(), String> {
+        // JSON5 snippets may end in `as const`, but not in an unterminated
+        // comment.
+        if self.mode == ParseMode::Json5 {
+            self.skip_whitespace()?;
+        }
+        if self.mode == ParseMode::Strict {
+            self.skip_whitespace()?;
+            if self.current().is_some() {
+                return Err(self.error_at("Unexpected trailing characters"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a single ABI array element. Non-object values and objects
+    /// without a `type` yield `None`, mirroring `parse_abi`.
+    pub fn parse_abi_item(&mut self) -> Result<Option<AbiItem>, String> {
+        let value = self.parse_value()?;
+        self.expect_end()?;
+        match value {
+            Value::Object(obj) => Ok(self.convert_to_abi_item(&obj)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parses a JSON array of strings, such as this tool's own JSON output.
+    pub fn parse_string_array(&mut self) -> Result<Vec<String>, String> {
+        self.skip_whitespace()?;
+        let values = self.parse_array()?;
+        self.expect_end()?;
+        values
+            .into_iter()
+            .map(|v| {
+                v.as_string()
+                    .ok_or_else(|| "Expected array of strings".to_string())
+            })
+            .collect()
+    }
+
+    pub fn parse_key(&mut self) -> Result<String, String> {
+        self.skip_whitespace()?;
+        match self.parse_string()? {
+            Value::String(s) => Ok(s),
+            _ => Err("Expected string key".to_string()),
         }
     }
 
@@ -125,16 +229,17 @@ impl JsonParser {
     }
 
     fn parse_value(&mut self) -> Result<Value, String> {
-        self.skip_whitespace();
+        self.skip_whitespace()?;
 
         match self.current() {
             Some('"') => self.parse_string(),
+            Some('\'') if self.mode == ParseMode::Json5 => self.parse_string(),
             Some('[') => Ok(Value::Array(self.parse_array()?)),
             Some('{') => Ok(Value::Object(self.parse_object()?)),
             Some('t') | Some('f') => self.parse_bool(),
             Some('n') => self.parse_null(),
             Some(c) if c.is_ascii_digit() || c == '-' => self.parse_number(),
-            _ => Err("Unexpected character".to_string()),
+            _ => Err(self.error_at("Unexpected character")),
         }
     }
 
@@ -142,7 +247,7 @@ impl JsonParser {
         self.expect('[')?;
         let mut arr = Vec::new();
 
-        self.skip_whitespace();
+        self.skip_whitespace()?;
         if self.current() == Some(']') {
             self.advance();
             return Ok(arr);
@@ -150,16 +255,20 @@ impl JsonParser {
 
         loop {
             arr.push(self.parse_value()?);
-            self.skip_whitespace();
+            self.skip_whitespace()?;
 
             if self.current() == Some(',') {
                 self.advance();
-                self.skip_whitespace();
+                self.skip_whitespace()?;
+                if self.mode == ParseMode::Json5 && self.current() == Some(']') {
+                    self.advance();
+                    break;
+                }
             } else if self.current() == Some(']') {
                 self.advance();
                 break;
             } else {
-                return Err("Expected ',' or ']' in array".to_string());
+                return Err(self.error_at("Expected ',' or ']' in array"));
             }
         }
 
@@ -170,78 +279,111 @@ impl JsonParser {
         self.expect('{')?;
         let mut obj = HashMap::new();
 
-        self.skip_whitespace();
+        self.skip_whitespace()?;
         if self.current() == Some('}') {
             self.advance();
             return Ok(obj);
         }
 
         loop {
-            self.skip_whitespace();
-            let key = match self.parse_string()? {
-                Value::String(s) => s,
-                _ => return Err("Expected string key".to_string()),
+            self.skip_whitespace()?;
+            let key_position = self.position;
+            let key = if self.mode == ParseMode::Json5 && self.current().is_some_and(is_ident_start)
+            {
+                self.parse_identifier()
+            } else {
+                match self.parse_string()? {
+                    Value::String(s) => s,
+                    _ => return Err("Expected string key".to_string()),
+                }
             };
 
-            self.skip_whitespace();
+            if self.mode == ParseMode::Strict && obj.contains_key(&key) {
+                self.position = key_position;
+                return Err(self.error_at(&format!("Duplicate key '{key}'")));
+            }
+
+            self.skip_whitespace()?;
             self.expect(':')?;
 
             let value = self.parse_value()?;
             obj.insert(key, value);
 
-            self.skip_whitespace();
+            self.skip_whitespace()?;
             if self.current() == Some(',') {
                 self.advance();
+                self.skip_whitespace()?;
+                if self.mode == ParseMode::Json5 && self.current() == Some('}') {
+                    self.advance();
+                    break;
+                }
             } else if self.current() == Some('}') {
                 self.advance();
                 break;
             } else {
-                return Err("Expected ',' or '}' in object".to_string());
+                return Err(self.error_at("Expected ',' or '}' in object"));
             }
         }
 
         Ok(obj)
     }
 
+    fn parse_identifier(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self
+            .current()
+            .filter(|&c| is_ident_start(c) || c.is_ascii_digit())
+        {
+            ident.push(c);
+            self.advance();
+        }
+        ident
+    }
+
     fn parse_string(&mut self) -> Result<Value, String> {
-        self.expect('"')?;
+        let quote = match self.current() {
+            Some('\'') if self.mode == ParseMode::Json5 => '\'',
+            _ => '"',
+        };
+        self.expect(quote)?;
         let mut string = String::new();
 
-        while self.position < self.input.len() {
+        loop {
             match self.current() {
-                Some('"') => {
+                Some(c) if c == quote => {
                     self.advance();
                     return Ok(Value::String(string));
                 }
                 Some('\\') => {
                     self.advance();
                     match self.current() {
+                        Some('"') => string.push('"'),
+                        Some('\'') if self.mode == ParseMode::Json5 => string.push('\''),
+                        Some('\\') => string.push('\\'),
+                        Some('/') => string.push('/'),
+                        Some('b') => string.push('\u{8}'),
+                        Some('f') => string.push('\u{c}'),
                         Some('n') => string.push('\n'),
                         Some('r') => string.push('\r'),
                         Some('t') => string.push('\t'),
-                        Some('\\') => string.push('\\'),
-                        Some('"') => string.push('"'),
                         Some('u') => {
                             self.advance();
-                            let hex: String = (0..4)
-                                .filter_map(|_| {
-                                    let c = self.current()?;
-                                    self.advance();
-                                    Some(c)
-                                })
-                                .collect();
-                            if let Ok(code) = u32::from_str_radix(&hex, 16) {
-                                if let Some(ch) = char::from_u32(code) {
-                                    string.push(ch);
-                                }
-                            }
+                            string.push(self.parse_unicode_escape()?);
                             continue;
                         }
-                        Some(c) => string.push(c),
-                        None => return Err("Unexpected end of string".to_string()),
+                        Some(c) => {
+                            return Err(self.error_at(&format!("Invalid escape sequence '\\{c}'")))
+                        }
+                        None => return Err("Unterminated string".to_string()),
                     }
                     self.advance();
                 }
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self.error_at(&format!(
+                        "Unescaped control character U+{:04X} in string",
+                        c as u32
+                    )));
+                }
                 Some(c) => {
                     string.push(c);
                     self.advance();
@@ -249,11 +391,73 @@ impl JsonParser {
                 None => return Err("Unterminated string".to_string()),
             }
         }
+    }
+
+    /// Decodes the hex digits following `\u`, combining a UTF-16 surrogate
+    /// pair into a single scalar value as RFC 8259 section 7 requires.
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let start = self.position - 2;
+        let high = self.parse_hex4()?;
+
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if self.current() != Some('\\') || self.input.get(self.position + 1) != Some(&'u') {
+                    self.position = start;
+                    return Err(self.error_at("Unpaired high surrogate in \\u escape"));
+                }
+                self.position += 2;
+                let low = self.parse_hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    self.position = start;
+                    return Err(self.error_at("Invalid low surrogate in \\u escape"));
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => {
+                self.position = start;
+                return Err(self.error_at("Unpaired low surrogate in \\u escape"));
+            }
+            code => code,
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error_at("Invalid \\u escape"))
+    }
 
-        Err("Unterminated string".to_string())
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .current()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error_at("Expected 4 hex digits in \\u escape"))?;
+            code = code * 16 + digit;
+            self.advance();
+        }
+        Ok(code)
     }
 
     fn parse_number(&mut self) -> Result<Value, String> {
+        let num_str = if self.mode == ParseMode::Strict {
+            self.scan_strict_number()?
+        } else {
+            self.scan_loose_number()
+        };
+
+        if num_str.contains('.') || num_str.contains('e') || num_str.contains('E') {
+            num_str
+                .parse::<f64>()
+                .map(Value::Float)
+                .map_err(|_| "Invalid number".to_string())
+        } else {
+            num_str
+                .parse::<i64>()
+                .map(Value::Int)
+                .or_else(|_| num_str.parse::<f64>().map(Value::Float))
+                .map_err(|_| "Invalid number".to_string())
+        }
+    }
+
+    fn scan_loose_number(&mut self) -> String {
         let mut num_str = String::new();
 
         if self.current() == Some('-') {
@@ -270,16 +474,53 @@ impl JsonParser {
             }
         }
 
-        if num_str.contains('.') || num_str.contains('e') || num_str.contains('E') {
-            num_str
-                .parse::<f64>()
-                .map(Value::Float)
-                .map_err(|_| "Invalid number".to_string())
-        } else {
-            num_str
-                .parse::<i64>()
-                .map(Value::Int)
-                .map_err(|_| "Invalid number".to_string())
+        num_str
+    }
+
+    /// Scans `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`.
+    fn scan_strict_number(&mut self) -> Result<String, String> {
+        let start = self.position;
+
+        if self.current() == Some('-') {
+            self.advance();
+        }
+
+        match self.current() {
+            Some('0') => {
+                self.advance();
+                if self.current().is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(self.error_at("Leading zeros are not allowed in numbers"));
+                }
+            }
+            Some(c) if c.is_ascii_digit() => self.skip_digits(),
+            _ => return Err(self.error_at("Expected digit in number")),
+        }
+
+        if self.current() == Some('.') {
+            self.advance();
+            if !self.current().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error_at("Expected digit after decimal point"));
+            }
+            self.skip_digits();
+        }
+
+        if matches!(self.current(), Some('e') | Some('E')) {
+            self.advance();
+            if matches!(self.current(), Some('+') | Some('-')) {
+                self.advance();
+            }
+            if !self.current().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error_at("Expected digit in exponent"));
+            }
+            self.skip_digits();
+        }
+
+        Ok(self.input[start..self.position].iter().collect())
+    }
+
+    fn skip_digits(&mut self) {
+        while self.current().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance();
         }
     }
 
@@ -316,14 +557,53 @@ impl JsonParser {
         true
     }
 
-    fn skip_whitespace(&mut self) {
+    fn skip_whitespace(&mut self) -> Result<(), String> {
         while let Some(c) = self.current() {
-            if c.is_whitespace() {
+            let is_whitespace = match self.mode {
+                ParseMode::Strict => matches!(c, ' ' | '\t' | '\n' | '\r'),
+                ParseMode::Standard | ParseMode::Json5 => c.is_whitespace(),
+            };
+            if is_whitespace {
                 self.advance();
+            } else if self.mode == ParseMode::Json5 && self.skip_comment()? {
+                continue;
             } else {
                 break;
             }
         }
+        Ok(())
+    }
+
+    /// Skips a `//` or `/* */` comment at the current position, returning
+    /// whether one was found. A block comment must be closed.
+    fn skip_comment(&mut self) -> Result<bool, String> {
+        if self.current() != Some('/') {
+            return Ok(false);
+        }
+
+        match self.input.get(self.position + 1) {
+            Some('/') => {
+                while self.current().is_some_and(|c| c != '\n') {
+                    self.advance();
+                }
+                Ok(true)
+            }
+            Some('*') => {
+                let start = self.position;
+                self.position += 2;
+                while !(self.current() == Some('*')
+                    && self.input.get(self.position + 1) == Some(&'/'))
+                {
+                    if self.current().is_none() {
+                        return Err(format!("Unterminated comment at position {start}"));
+                    }
+                    self.advance();
+                }
+                self.position += 2;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 
     fn current(&self) -> Option<char> {
@@ -334,16 +614,77 @@ impl JsonParser {
         self.position += 1;
     }
 
+    fn error_at(&self, message: &str) -> String {
+        format!("{message} at position {}", self.position)
+    }
+
     fn expect(&mut self, expected: char) -> Result<(), String> {
         if self.current() == Some(expected) {
             self.advance();
             Ok(())
         } else {
-            Err(format!("Expected '{expected}'"))
+            Err(self.error_at(&format!("Expected '{expected}'")))
         }
     }
 }
 
+fn collect_natspec(doc: &HashMap<String, Value>, natspec: &mut NatSpec) {
+    let text = |key: &str| doc.get(key).and_then(Value::as_string);
+    natspec.title = natspec.title.take().or_else(|| text("title"));
+    natspec.notice = natspec.notice.take().or_else(|| text("notice"));
+    natspec.details = natspec.details.take().or_else(|| text("details"));
+
+    for (section, kind) in [
+        ("methods", "function"),
+        ("events", "event"),
+        ("errors", "error"),
+    ] {
+        let Some(Value::Object(entries)) = doc.get(section) else {
+            continue;
+        };
+        for (signature, value) in entries {
+            let key = if signature == "constructor" {
+                doc_key("constructor", "")
+            } else {
+                doc_key(kind, signature)
+            };
+            // Errors can be documented once per definition, so their
+            // entries are arrays.
+            let values = match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                if let Value::Object(entry) = value {
+                    merge_doc(entry, natspec.items.entry(key.clone()).or_default());
+                }
+            }
+        }
+    }
+}
+
+fn merge_doc(entry: &HashMap<String, Value>, doc: &mut Doc) {
+    if let Some(notice) = entry.get("notice").and_then(Value::as_string) {
+        doc.notice = Some(notice);
+    }
+    if let Some(details) = entry.get("details").and_then(Value::as_string) {
+        doc.details = Some(details);
+    }
+    for (key, target) in [("params", &mut doc.params), ("returns", &mut doc.returns)] {
+        if let Some(Value::Object(described)) = entry.get(key) {
+            for (name, text) in described {
+                if let Some(text) = text.as_string() {
+                    target.insert(name.clone(), text);
+                }
+            }
+        }
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 enum Value {
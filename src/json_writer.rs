@@ -0,0 +1,78 @@
+use crate::converter::escape_json_string;
+
+/// Minimal JSON document model for output. Objects keep insertion order so
+/// emitted keys are stable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    String(String),
+    Bool(bool),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    pub fn string(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+
+    pub fn render(&self, pretty: bool) -> String {
+        let mut out = String::new();
+        self.write(&mut out, pretty, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, pretty: bool, indent: usize) {
+        match self {
+            JsonValue::String(s) => {
+                out.push('"');
+                out.push_str(&escape_json_string(s));
+                out.push('"');
+            }
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Array(values) => {
+                if values.is_empty() {
+                    out.push_str("[]");
+                    return;
+                }
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, pretty, indent + 1);
+                    value.write(out, pretty, indent + 1);
+                }
+                newline(out, pretty, indent);
+                out.push(']');
+            }
+            JsonValue::Object(entries) => {
+                if entries.is_empty() {
+                    out.push_str("{}");
+                    return;
+                }
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, pretty, indent + 1);
+                    out.push('"');
+                    out.push_str(&escape_json_string(key));
+                    out.push_str(if pretty { "\": " } else { "\":" });
+                    value.write(out, pretty, indent + 1);
+                }
+                newline(out, pretty, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn newline(out: &mut String, pretty: bool, indent: usize) {
+    if pretty {
+        out.push('\n');
+        for _ in 0..indent {
+            out.push_str("  ");
+        }
+    }
+}
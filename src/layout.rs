@@ -0,0 +1,94 @@
+use crate::abi::AbiItem;
+use crate::canonical::effective_mutability;
+
+/// Order of items within the output, or within each group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// The order the compiler emitted.
+    #[default]
+    Source,
+    /// Alphabetically by name, overloads by canonical signature.
+    Name,
+    /// By 4-byte selector, or topic for events.
+    Selector,
+}
+
+impl SortOrder {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "source" | "none" => Ok(SortOrder::Source),
+            "name" => Ok(SortOrder::Name),
+            "selector" => Ok(SortOrder::Selector),
+            _ => Err(format!(
+                "Unknown sort order '{name}' (expected name or selector)"
+            )),
+        }
+    }
+}
+
+/// How items are arranged in json, structured and raw output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Layout {
+    pub group: bool,
+    pub sort: SortOrder,
+}
+
+/// Group labels in output order.
+const GROUPS: [&str; 7] = [
+    "constructor",
+    "fallback/receive",
+    "view/pure",
+    "nonpayable",
+    "payable",
+    "events",
+    "errors",
+];
+
+pub fn sort_items(abi: &[AbiItem], order: SortOrder) -> Vec<AbiItem> {
+    let mut items = abi.to_vec();
+    match order {
+        SortOrder::Source => {}
+        SortOrder::Name => items.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| a.canonical_signature().cmp(&b.canonical_signature()))
+        }),
+        SortOrder::Selector => items.sort_by_cached_key(|item| {
+            item.selector()
+                .map(|s| s.to_vec())
+                .or_else(|| item.topic().map(|t| t.to_vec()))
+                .unwrap_or_default()
+        }),
+    }
+    items
+}
+
+/// Splits items into labelled groups by kind and mutability, keeping their
+/// relative order and leaving out empty groups.
+pub fn group_items(abi: &[AbiItem]) -> Vec<(String, Vec<AbiItem>)> {
+    let mut groups: Vec<(String, Vec<AbiItem>)> = GROUPS
+        .iter()
+        .map(|label| (label.to_string(), Vec::new()))
+        .collect();
+
+    for item in abi {
+        let label = match item.r#type.as_str() {
+            "constructor" => "constructor",
+            "fallback" | "receive" => "fallback/receive",
+            "event" => "events",
+            "error" => "errors",
+            "function" => match effective_mutability(item) {
+                "view" | "pure" => "view/pure",
+                "payable" => "payable",
+                _ => "nonpayable",
+            },
+            _ => continue,
+        };
+        if let Some((_, items)) = groups.iter_mut().find(|(l, _)| l == label) {
+            items.push(item.clone());
+        }
+    }
+
+    groups.retain(|(_, items)| !items.is_empty());
+    groups
+}
@@ -1,14 +1,50 @@
 mod abi;
+mod abi_codec;
+mod canonical;
+mod codegen;
+mod commands;
+mod compact;
+mod conformance;
 mod converter;
+mod dedupe;
+mod diff;
 mod file_ops;
+mod filter;
+mod git;
+mod go;
+mod html;
+mod interfaces;
 mod json_parser;
+mod json_writer;
+mod keccak;
+mod layout;
+mod markdown;
+mod multicall;
+mod natspec;
+mod python;
+mod rust;
+mod signature_parser;
+mod solidity;
+mod stream;
+mod structured;
 mod tests;
-
-use converter::Converter;
-use file_ops::{convert_directory, convert_file, convert_stdin_to_stdout, ConvertOptions};
+mod ts_import;
+mod typescript;
+
+use commands::{is_command, run_command, CommandOptions};
+use compact::savings_summary;
+use converter::{Converter, OutputFormat};
+use file_ops::{
+    combine_directory, contract_name, convert_directory, convert_file, convert_stdin_to_stdout,
+    convert_stream, ConvertOptions,
+};
+use filter::Filter;
+use json_parser::ParseMode;
+use layout::{Layout, SortOrder};
 use std::env;
 use std::path::Path;
 use std::process;
+use ts_import::{extract_exported_abis, is_script_file};
 
 const VERSION: &str = "1.0.2";
 
@@ -20,21 +56,74 @@ Optimized for AI agents to efficiently consume smart contract interfaces
 
 USAGE:
   abi2human [options] <input> [output]
+  abi2human <command> [options] <args>
 
 ARGUMENTS:
-  input    Input ABI file (.json) or directory
+  input    Input ABI file (.json, or .ts/.js with `export const` ABIs)
+           or directory
   output   Output file or directory (optional)
 
+COMMANDS:
+  multicall <abi> <address>           Multicall3 aggregate3 calldata reading every
+                                      zero-argument view/pure function
+  multicall-decode <abi> [result]     Decode the aggregate3 result (hex, file or
+                                      stdin) into a named table
+  interfaces <abi>                    Which standard interfaces (ERC-20, 721, 1155,
+                                      4626, 2612, 165...) are implemented fully,
+                                      partially or not at all
+  check <abi> --against <reference>   Report reference functions, events and errors
+                                      the ABI lacks or declares differently; exits
+                                      non-zero on any mismatch
+  diff <old> <new>                    Added, removed and changed functions, events
+                                      and errors, flagging breaking changes; exits
+                                      non-zero if any are breaking
+  diff --git <from>..<to> [path]      Diff every .json artifact under path between
+                                      two revisions of the local git repository,
+                                      with a breaking-change summary per contract
+                                      (`abi2human --git <range> [path]` for short)
+
+  A file or directory named like a command is converted when it is the only
+  argument (`abi2human diff`); use `./diff` with an output path.
+
 OPTIONS:
   -o, --stdout     Output to stdout
-  -r, --raw        Output raw text format instead of JSON
+  -r, --raw        Output raw text format instead of JSON (same as --format raw)
+  -f, --format     Output format: json (default), raw, structured, ndjson
+                   (one file per directory), canonical, solidity, markdown,
+                   html (one report per directory), typescript, rust,
+                   python, go, compact
+  --compact        Token-minimal output grouped by mutability, with the size
+                   saving reported on stderr (same as --format compact)
+  --no-names       Drop parameter names from --compact output
+  --group          Group json, structured and raw output by kind and mutability
+  --combine        Convert a directory into one document (index.<ext> in the
+                   output directory, or stdout with -o)
+  --dedupe         With --combine, list items shared by several contracts
+                   once in `common` sections that each contract references
+                   (json, raw and compact output)
+  --sort <order>   Sort items within the output or each group: name, selector
+  --only <kinds>   Keep only these kinds: functions, events, errors,
+                   constructor, fallback, receive (`!kind` excludes)
+  --mutability <m> Keep only view, pure, nonpayable or payable items
+  --name <glob>    Keep items whose name matches, e.g. 'get*'
+  --selector <hex> Keep the item with this selector or event topic (prefix)
   -h, --help       Show this help message
   -v, --version    Show version
   -q, --quiet      Suppress non-output messages
-  -d, --dir        Process directory
-  -p, --pattern    Glob pattern for filtering files
+  -d, --dir        Process directory, including subdirectories other than
+                   build-info/ and cache/
+  -p, --pattern    Glob pattern for filtering files; directories only include
+                   .ts/.js modules when a pattern is given and matches them
   -s, --suffix     Custom suffix for output files (default: ".readable")
   --no-pretty      Disable pretty-printing
+  --strict         Reject JSON that does not conform to RFC 8259 (with --stream,
+                   only ABI items and object keys are checked)
+  --json5          Accept comments, trailing commas, single quotes and
+                   unquoted keys (e.g. viem `as const` ABI snippets)
+  --reverse        Convert human-readable signatures (one per line, or a
+                   JSON array of strings) back to a JSON ABI
+  --stream         Stream items from very large inputs (solc standard-JSON,
+                   build-info) with bounded memory; reads stdin if no input
 
 EXAMPLES:
   # Quick ABI inspection
@@ -49,13 +138,47 @@ EXAMPLES:
   # Batch convert directory
   abi2human ./abis/ -d ./readable/
 
+  # Convert every `export const fooAbi = [...] as const` in a module
+  abi2human src/abis.ts -or
+
+  # Normalised JSON ABI for checking into git
+  abi2human out/Token.sol/Token.json abi/Token.json -f canonical
+
+  # Hand-write a minimal ABI and expand it to JSON
+  abi2human signatures.txt --reverse -o
+
+  # Snapshot a contract's state in one eth_call
+  abi2human multicall Token.json 0x6B175474E89094C44Da98b954EedeAC495271d0F -r
+  abi2human multicall-decode Token.json result.hex -r
+
+  # Read functions first, alphabetically
+  abi2human Token.json -or --group --sort name
+
+  # Only the read surface of a large contract
+  abi2human Router.json -or --mutability view,pure
+
+  # A whole directory as one JSON object keyed by contract name
+  abi2human ./abis -o --combine
+
+  # Every item of a project as JSON Lines, for grep and jq
+  abi2human out/ -o -f ndjson | jq 'select(.stateMutability == "payable")'
+
+  # Stream a large build-info file
+  abi2human out/build-info/abc.json --stream -r
+
+  # Breaking ABI changes in the last commit
+  abi2human --git HEAD~1..HEAD out/ -r
+
 FOR AI AGENTS:
   This tool helps you read Ethereum ABIs efficiently without consuming excessive tokens.
+  Use --compact (and --no-names) for the smallest output.
 "#
     );
 }
 
 struct CliArgs {
+    command: Option<String>,
+    command_args: Vec<String>,
     input: Option<String>,
     output: Option<String>,
     stdout: bool,
@@ -64,7 +187,24 @@ struct CliArgs {
     directory: bool,
     pattern: Option<String>,
     suffix: String,
+    format: Option<String>,
     pretty: bool,
+    stream: bool,
+    strict: bool,
+    json5: bool,
+    reverse: bool,
+    compact: bool,
+    no_names: bool,
+    group: bool,
+    sort: Option<String>,
+    combine: bool,
+    dedupe: bool,
+    against: Option<String>,
+    git: Option<String>,
+    only: Vec<String>,
+    mutability: Vec<String>,
+    names: Vec<String>,
+    selectors: Vec<String>,
     help: bool,
     version: bool,
 }
@@ -73,6 +213,8 @@ impl CliArgs {
     fn parse() -> Self {
         let args: Vec<String> = env::args().collect();
         let mut cli_args = CliArgs {
+            command: None,
+            command_args: Vec::new(),
             input: None,
             output: None,
             stdout: false,
@@ -81,7 +223,24 @@ impl CliArgs {
             directory: false,
             pattern: None,
             suffix: ".readable".to_string(),
+            format: None,
             pretty: true,
+            stream: false,
+            strict: false,
+            json5: false,
+            reverse: false,
+            compact: false,
+            no_names: false,
+            group: false,
+            sort: None,
+            combine: false,
+            dedupe: false,
+            against: None,
+            git: None,
+            only: Vec::new(),
+            mutability: Vec::new(),
+            names: Vec::new(),
+            selectors: Vec::new(),
             help: false,
             version: false,
         };
@@ -101,12 +260,57 @@ impl CliArgs {
                     "-q" | "--quiet" => cli_args.quiet = true,
                     "-d" | "--dir" => cli_args.directory = true,
                     "--no-pretty" => cli_args.pretty = false,
+                    "--stream" => cli_args.stream = true,
+                    "--strict" => cli_args.strict = true,
+                    "--json5" => cli_args.json5 = true,
+                    "--reverse" => cli_args.reverse = true,
+                    "--compact" => cli_args.compact = true,
+                    "--no-names" => cli_args.no_names = true,
+                    "--group" => cli_args.group = true,
+                    "--combine" => cli_args.combine = true,
+                    "--dedupe" => cli_args.dedupe = true,
+                    "--against" => {
+                        i += 1;
+                        if i < args.len() {
+                            cli_args.against = Some(args[i].clone());
+                        }
+                    }
+                    "--git" => {
+                        i += 1;
+                        if i < args.len() {
+                            cli_args.git = Some(args[i].clone());
+                        }
+                    }
+                    "--sort" => {
+                        i += 1;
+                        if i < args.len() {
+                            cli_args.sort = Some(args[i].clone());
+                        }
+                    }
+                    "--only" | "--mutability" | "--name" | "--selector" => {
+                        i += 1;
+                        if i < args.len() {
+                            let list = match arg.as_str() {
+                                "--only" => &mut cli_args.only,
+                                "--mutability" => &mut cli_args.mutability,
+                                "--name" => &mut cli_args.names,
+                                _ => &mut cli_args.selectors,
+                            };
+                            list.push(args[i].clone());
+                        }
+                    }
                     "-p" | "--pattern" => {
                         i += 1;
                         if i < args.len() {
                             cli_args.pattern = Some(args[i].clone());
                         }
                     }
+                    "-f" | "--format" => {
+                        i += 1;
+                        if i < args.len() {
+                            cli_args.format = Some(args[i].clone());
+                        }
+                    }
                     "-s" | "--suffix" => {
                         i += 1;
                         if i < args.len() {
@@ -125,6 +329,19 @@ impl CliArgs {
             i += 1;
         }
 
+        if is_command(Path::new("."), &positionals) {
+            cli_args.command = Some(positionals.remove(0));
+            cli_args.command_args = positionals;
+            return cli_args;
+        }
+
+        // `abi2human --git <range> [path]` is shorthand for `diff --git`.
+        if cli_args.git.is_some() {
+            cli_args.command = Some("diff".to_string());
+            cli_args.command_args = positionals;
+            return cli_args;
+        }
+
         if !positionals.is_empty() {
             cli_args.input = Some(positionals[0].clone());
         }
@@ -155,6 +372,141 @@ fn main() {
         }
     };
 
+    let mode = match (args.strict, args.json5) {
+        (true, true) => {
+            eprintln!("Error: --strict and --json5 cannot be combined");
+            process::exit(1);
+        }
+        (true, false) => ParseMode::Strict,
+        (false, true) => ParseMode::Json5,
+        (false, false) => ParseMode::Standard,
+    };
+
+    let format = match args.format.as_deref().map(OutputFormat::parse) {
+        Some(Ok(format)) if args.raw && format != OutputFormat::Raw => {
+            eprintln!("Error: --raw cannot be combined with another --format");
+            process::exit(1);
+        }
+        Some(Ok(format)) if args.compact && !matches!(format, OutputFormat::Compact { .. }) => {
+            eprintln!("Error: --compact cannot be combined with another --format");
+            process::exit(1);
+        }
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        None if args.raw && args.compact => {
+            eprintln!("Error: --raw cannot be combined with --compact");
+            process::exit(1);
+        }
+        None if args.raw => OutputFormat::Raw,
+        None if args.compact => OutputFormat::Compact { names: true },
+        None => OutputFormat::Json,
+    };
+
+    let format = match format {
+        OutputFormat::Compact { .. } => OutputFormat::Compact {
+            names: !args.no_names,
+        },
+        _ if args.no_names => {
+            eprintln!("Error: --no-names only applies to --compact output");
+            process::exit(1);
+        }
+        other => other,
+    };
+    let compact = matches!(format, OutputFormat::Compact { .. });
+
+    let layout = Layout {
+        group: args.group,
+        sort: match args.sort.as_deref().map(SortOrder::parse) {
+            Some(Ok(sort)) => sort,
+            Some(Err(e)) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+            None => SortOrder::Source,
+        },
+    };
+
+    if args.against.is_some() && args.command.as_deref() != Some("check") {
+        eprintln!("Error: --against only applies to the check command");
+        process::exit(1);
+    }
+    if args.git.is_some() && args.command.as_deref() != Some("diff") {
+        eprintln!("Error: --git only applies to the diff command");
+        process::exit(1);
+    }
+
+    if args.dedupe && !args.combine {
+        eprintln!("Error: --dedupe only applies to --combine output");
+        process::exit(1);
+    }
+    if args.dedupe
+        && !matches!(
+            format,
+            OutputFormat::Json | OutputFormat::Raw | OutputFormat::Compact { .. }
+        )
+    {
+        eprintln!("Error: --dedupe only applies to json, raw and compact output");
+        process::exit(1);
+    }
+
+    let filter = match build_filter(&args) {
+        Ok(filter) => filter,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(command) = &args.command {
+        let options = CommandOptions {
+            raw: args.raw,
+            pretty: args.pretty,
+            mode,
+            against: args.against.clone(),
+            git: args.git.clone(),
+        };
+        if let Err(e) = run_command(command, &args.command_args, &options) {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.reverse {
+        if let Err(e) = run_reverse(args.input.as_deref(), args.output.as_deref(), args.pretty) {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.stream {
+        if mode == ParseMode::Json5 {
+            eprintln!("Error: --json5 cannot be combined with --stream");
+            process::exit(1);
+        }
+        if layout != Layout::default() {
+            eprintln!("Error: --group and --sort cannot be combined with --stream");
+            process::exit(1);
+        }
+
+        let options = ConvertOptions {
+            pretty: args.pretty,
+            format,
+            mode,
+            filter,
+            ..ConvertOptions::default()
+        };
+        if let Err(e) = run_stream(args.input.as_deref(), args.output.as_deref(), &options) {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        return;
+    }
+
     if let Some(input) = args.input {
         let input_path = Path::new(&input);
 
@@ -167,15 +519,37 @@ fn main() {
             suffix: args.suffix,
             pretty: args.pretty,
             pattern: args.pattern,
+            format,
+            mode,
+            layout,
+            filter,
+            combine: args.combine,
+            dedupe: args.dedupe,
         };
 
-        if input_path.is_dir() {
-            let output_dir = if let Some(output) = args.output {
-                Path::new(&output).to_path_buf()
-            } else {
-                input_path.join("readable")
-            };
+        let output_dir = if let Some(output) = &args.output {
+            Path::new(output).to_path_buf()
+        } else {
+            input_path.join("readable")
+        };
 
+        if input_path.is_dir() && args.stdout && (args.combine || format.combined()) {
+            let (document, results) = combine_directory(input_path, &output_dir, &options);
+            if let Some(document) = document {
+                println!("{document}");
+            }
+
+            let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
+            if !failed.is_empty() {
+                eprintln!("❌ Failed to convert {} files:", failed.len());
+                for result in failed {
+                    if let Some(error) = &result.error {
+                        eprintln!("  - {}: {}", result.input_path.display(), error);
+                    }
+                }
+                process::exit(1);
+            }
+        } else if input_path.is_dir() {
             log(&format!(
                 "🔄 Converting ABI files from {} to {}",
                 input_path.display(),
@@ -192,6 +566,15 @@ fn main() {
                     "✅ Successfully converted {} files",
                     successful.len()
                 ));
+                if compact {
+                    let input_bytes = successful.iter().map(|r| file_size(&r.input_path)).sum();
+                    let output_bytes = successful
+                        .iter()
+                        .filter_map(|r| r.output_path.as_deref())
+                        .map(file_size)
+                        .sum();
+                    log(&savings_summary(input_bytes, output_bytes));
+                }
             }
 
             if !failed.is_empty() {
@@ -212,31 +595,57 @@ fn main() {
                 }
             };
 
-            let abi_items = match Converter::parse_abi_content(&content) {
-                Ok(items) => items,
-                Err(e) => {
-                    eprintln!("Error parsing ABI: {e}");
+            let formatted = if is_script_file(input_path) {
+                let exports = match extract_exported_abis(&content) {
+                    Ok(exports) => exports,
+                    Err(e) => {
+                        eprintln!("Error parsing ABI: {e}");
+                        process::exit(1);
+                    }
+                };
+
+                if exports.is_empty() {
+                    eprintln!("Error: No exported ABI arrays found in file");
                     process::exit(1);
                 }
-            };
-
-            if abi_items.is_empty() {
-                eprintln!("Error: No valid ABI found in file");
-                process::exit(1);
-            }
 
-            let human_readable = Converter::convert_to_human_readable(&abi_items);
-
-            if args.raw {
-                for item in human_readable {
-                    println!("{item}");
-                }
+                let exports: Vec<_> = exports
+                    .into_iter()
+                    .map(|(name, items)| (name, options.filter.apply(&items)))
+                    .collect();
+                Converter::format_exports(&exports, format, args.pretty, layout)
             } else {
-                let formatted = Converter::format_as_json_array(&human_readable, args.pretty);
-                print!("{formatted}");
-                if args.pretty {
-                    println!();
+                let abi_items = match Converter::parse_abi_content_with_mode(&content, options.mode)
+                {
+                    Ok(items) => items,
+                    Err(e) => {
+                        eprintln!("Error parsing ABI: {e}");
+                        process::exit(1);
+                    }
+                };
+
+                if abi_items.is_empty() {
+                    eprintln!("Error: No valid ABI found in file");
+                    process::exit(1);
                 }
+
+                Converter::format_contract(
+                    &contract_name(input_path),
+                    &abi_items,
+                    &Converter::parse_natspec(&content, mode, format),
+                    format,
+                    args.pretty,
+                    layout,
+                    &options.filter,
+                )
+            };
+
+            print!("{formatted}");
+            if format != OutputFormat::Json || args.pretty {
+                println!();
+            }
+            if compact {
+                log(&savings_summary(content.len(), formatted.len()));
             }
         } else {
             let output_path = args.output.as_ref().map(Path::new);
@@ -250,6 +659,12 @@ fn main() {
                         output.display(),
                         result.item_count.unwrap_or(0)
                     ));
+                    if compact {
+                        log(&savings_summary(
+                            file_size(&result.input_path),
+                            file_size(&output),
+                        ));
+                    }
                 }
             } else {
                 if let Some(error) = result.error {
@@ -259,10 +674,102 @@ fn main() {
             }
         }
     } else {
-        let options = ConvertOptions::default();
-        if let Err(e) = convert_stdin_to_stdout(&options) {
-            eprintln!("Error: {e}");
-            process::exit(1);
+        let options = ConvertOptions {
+            pretty: args.pretty,
+            format,
+            mode,
+            layout,
+            filter,
+            ..ConvertOptions::default()
+        };
+        match convert_stdin_to_stdout(&options) {
+            Ok((input_bytes, output_bytes)) if compact => {
+                log(&savings_summary(input_bytes, output_bytes));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn file_size(path: &Path) -> usize {
+    std::fs::metadata(path).map_or(0, |m| m.len() as usize)
+}
+
+fn run_reverse(input: Option<&str>, output: Option<&str>, pretty: bool) -> Result<(), String> {
+    let content = match input {
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {e}"))?
+        }
+        None => {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            content
+        }
+    };
+
+    let abi_items = Converter::parse_human_readable(&content)?;
+    let formatted = Converter::format_as_abi_json(&abi_items, pretty);
+
+    match output {
+        Some(path) => std::fs::write(path, format!("{formatted}\n"))
+            .map_err(|e| format!("Failed to write file: {e}")),
+        None => {
+            println!("{formatted}");
+            Ok(())
+        }
+    }
+}
+
+/// Collects the `--only`, `--mutability`, `--name` and `--selector` lists.
+fn build_filter(args: &CliArgs) -> Result<Filter, String> {
+    let mut filter = Filter::default();
+    for list in &args.only {
+        filter.add_kinds(list)?;
+    }
+    for list in &args.mutability {
+        filter.add_mutability(list)?;
+    }
+    for list in &args.names {
+        filter.add_names(list);
+    }
+    for list in &args.selectors {
+        filter.add_selectors(list)?;
+    }
+    Ok(filter)
+}
+
+fn run_stream(
+    input: Option<&str>,
+    output: Option<&str>,
+    options: &ConvertOptions,
+) -> std::io::Result<usize> {
+    let reader: Box<dyn std::io::Read> = match input {
+        Some(path) => Box::new(std::fs::File::open(path)?),
+        None => Box::new(std::io::stdin().lock()),
+    };
+
+    match output {
+        // Written to a temporary file first, so a stream that fails part
+        // way through never leaves a truncated document at `path`.
+        Some(path) => {
+            let partial = format!("{path}.partial");
+            let file = std::fs::File::create(&partial)?;
+            match convert_stream(reader, std::io::BufWriter::new(file), options) {
+                Ok(written) => {
+                    std::fs::rename(&partial, path)?;
+                    Ok(written)
+                }
+                Err(e) => {
+                    let _ = std::fs::remove_file(&partial);
+                    Err(e)
+                }
+            }
         }
+        None => convert_stream(reader, std::io::stdout().lock(), options),
     }
 }
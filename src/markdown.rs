@@ -0,0 +1,137 @@
+use crate::abi::AbiItem;
+use crate::canonical::effective_mutability;
+use crate::natspec::{Doc, NatSpec};
+
+/// A row of a parameter table: name, type and NatSpec description.
+type Row = (String, String, Option<String>);
+
+/// A document section: its heading and which items it lists.
+type Section = (&'static str, fn(&AbiItem) -> bool);
+
+const SECTIONS: [Section; 6] = [
+    ("Constructor", |item| item.r#type == "constructor"),
+    ("Read Functions", |item| item.is_read_only()),
+    ("Write Functions", |item| {
+        is_callable(item) && effective_mutability(item) == "nonpayable"
+    }),
+    ("Payable Functions", |item| {
+        is_callable(item) && effective_mutability(item) == "payable"
+    }),
+    ("Events", |item| item.r#type == "event"),
+    ("Errors", |item| item.r#type == "error"),
+];
+
+/// Renders one contract's ABI as a Markdown document, grouping items the
+/// way an auditor reads a contract surface.
+pub fn render_markdown(name: &str, abi: &[AbiItem], docs: &NatSpec) -> String {
+    let mut out = format!("# {name}\n");
+    for text in [&docs.title, &docs.notice, &docs.details]
+        .into_iter()
+        .flatten()
+    {
+        out.push_str(&format!("\n{text}\n"));
+    }
+
+    for (title, belongs) in SECTIONS {
+        let items: Vec<&AbiItem> = abi.iter().filter(|item| belongs(item)).collect();
+        if items.is_empty() {
+            continue;
+        }
+
+        out.push_str(&format!("\n## {title}\n"));
+        for item in items {
+            render_item(&mut out, item, docs.for_item(item));
+        }
+    }
+
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Functions, fallback and receive: the items that can be called.
+fn is_callable(item: &AbiItem) -> bool {
+    matches!(item.r#type.as_str(), "function" | "fallback" | "receive") && !item.is_read_only()
+}
+
+fn render_item(out: &mut String, item: &AbiItem, doc: Option<&Doc>) {
+    if item.r#type != "constructor" {
+        let heading = item.name.as_deref().unwrap_or(&item.r#type);
+        out.push_str(&format!("\n### {heading}\n"));
+    }
+    out.push_str(&format!("\n```solidity\n{item}\n```\n"));
+
+    if let Some(doc) = doc {
+        for text in [&doc.notice, &doc.details].into_iter().flatten() {
+            out.push_str(&format!("\n{text}\n"));
+        }
+    }
+
+    let inputs: Vec<Row> = item
+        .inputs
+        .iter()
+        .flatten()
+        .map(|input| {
+            let name = input.name.clone().unwrap_or_default();
+            let mut r#type = format!("`{}`", input.canonical_type());
+            if input.indexed == Some(true) {
+                r#type.push_str(" indexed");
+            }
+            let description = doc.and_then(|d| d.params.get(&name)).cloned();
+            (name, r#type, description)
+        })
+        .collect();
+    render_table(out, "Parameter", &inputs);
+
+    let outputs: Vec<Row> = item
+        .outputs
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, output)| {
+            let name = output.name.clone().unwrap_or_default();
+            let description = doc.and_then(|d| {
+                d.returns
+                    .get(&name)
+                    .or_else(|| d.returns.get(&format!("_{i}")))
+                    .cloned()
+            });
+            (name, format!("`{}`", output.canonical_type()), description)
+        })
+        .collect();
+    render_table(out, "Returns", &outputs);
+}
+
+/// Writes a parameter table, with a description column only when at least
+/// one row has NatSpec.
+fn render_table(out: &mut String, heading: &str, rows: &[Row]) {
+    if rows.is_empty() {
+        return;
+    }
+
+    let described = rows.iter().any(|(_, _, description)| description.is_some());
+    if described {
+        out.push_str(&format!(
+            "\n| {heading} | Type | Description |\n| --- | --- | --- |\n"
+        ));
+    } else {
+        out.push_str(&format!("\n| {heading} | Type |\n| --- | --- |\n"));
+    }
+
+    for (name, ty, description) in rows {
+        let name = if name.is_empty() {
+            "-".to_string()
+        } else {
+            format!("`{name}`")
+        };
+        if described {
+            let description = description.as_deref().map(escape_cell).unwrap_or_default();
+            out.push_str(&format!("| {name} | {ty} | {description} |\n"));
+        } else {
+            out.push_str(&format!("| {name} | {ty} |\n"));
+        }
+    }
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
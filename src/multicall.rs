@@ -0,0 +1,133 @@
+use crate::abi::AbiItem;
+use crate::abi_codec::{
+    decode_values, encode_usize, from_hex, read_bytes, read_offset, read_usize, read_word, to_hex,
+    ParamType,
+};
+
+/// Multicall3 is deployed at the same address on every major chain.
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// Selector of `aggregate3((address,bool,bytes)[])`.
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// One decoded row of a snapshot.
+pub struct SnapshotEntry {
+    pub name: String,
+    pub success: bool,
+    /// Decoded outputs as `(name, value)` pairs, or the raw revert data
+    /// under an empty name when the call failed.
+    pub values: Vec<(String, String)>,
+}
+
+/// The functions a snapshot covers: every `view`/`pure` function that takes
+/// no arguments, in ABI order.
+pub fn snapshot_functions(abi: &[AbiItem]) -> Vec<&AbiItem> {
+    abi.iter()
+        .filter(|item| item.is_read_only())
+        .filter(|item| item.inputs.as_ref().is_none_or(|inputs| inputs.is_empty()))
+        .collect()
+}
+
+/// Builds `aggregate3` calldata calling every snapshot function on `target`
+/// with `allowFailure` set, so one reverting getter doesn't sink the rest.
+pub fn encode_snapshot(abi: &[AbiItem], target: &str) -> Result<Vec<u8>, String> {
+    let address = parse_address(target)?;
+    let functions = snapshot_functions(abi);
+    if functions.is_empty() {
+        return Err("No view or pure functions without arguments found".to_string());
+    }
+
+    // Each Call3 tuple is five words: target, allowFailure, the offset of
+    // callData, its length and the selector padded to a full word.
+    const CALL_SIZE: usize = 5 * 32;
+
+    let mut data = AGGREGATE3_SELECTOR.to_vec();
+    data.extend_from_slice(&encode_usize(32));
+    data.extend_from_slice(&encode_usize(functions.len()));
+    for i in 0..functions.len() {
+        data.extend_from_slice(&encode_usize(functions.len() * 32 + i * CALL_SIZE));
+    }
+
+    for function in functions {
+        let selector = function
+            .selector()
+            .ok_or_else(|| "Function without a name".to_string())?;
+
+        let mut target_word = [0u8; 32];
+        target_word[12..].copy_from_slice(&address);
+        data.extend_from_slice(&target_word);
+        data.extend_from_slice(&encode_usize(1));
+        data.extend_from_slice(&encode_usize(3 * 32));
+        data.extend_from_slice(&encode_usize(selector.len()));
+        let mut selector_word = [0u8; 32];
+        selector_word[..4].copy_from_slice(&selector);
+        data.extend_from_slice(&selector_word);
+    }
+
+    Ok(data)
+}
+
+/// Decodes the `(bool success, bytes returnData)[]` returned by `aggregate3`
+/// for calldata built by `encode_snapshot` from the same ABI.
+pub fn decode_snapshot(abi: &[AbiItem], data: &[u8]) -> Result<Vec<SnapshotEntry>, String> {
+    let functions = snapshot_functions(abi);
+
+    let array = read_offset(data, 0, 0)?;
+    let len = read_usize(data, array)?;
+    if len != functions.len() {
+        return Err(format!(
+            "Result has {len} entries but the ABI has {} snapshot functions",
+            functions.len()
+        ));
+    }
+
+    let elements = array + 32;
+    let mut entries = Vec::with_capacity(len);
+
+    for (i, function) in functions.into_iter().enumerate() {
+        let result = read_offset(data, elements + i * 32, elements)?;
+        let success = read_word(data, result)?[31] != 0;
+        let return_data = read_bytes(data, read_offset(data, result + 32, result)?)?;
+
+        let name = function.name.clone().unwrap_or_default();
+        let signature = function.canonical_signature().unwrap_or_default();
+
+        let values = if success {
+            let outputs = function.outputs.as_deref().unwrap_or(&[]);
+            let types = outputs
+                .iter()
+                .map(ParamType::from_output)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("{signature}: {e}"))?;
+            let decoded =
+                decode_values(&types, return_data).map_err(|e| format!("{signature}: {e}"))?;
+            outputs
+                .iter()
+                .map(|o| o.name.clone().unwrap_or_default())
+                .zip(decoded)
+                .collect()
+        } else {
+            vec![(String::new(), to_hex(return_data))]
+        };
+
+        entries.push(SnapshotEntry {
+            name,
+            success,
+            values,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn parse_address(address: &str) -> Result<[u8; 20], String> {
+    let bytes = from_hex(address).map_err(|e| format!("Invalid address '{address}': {e}"))?;
+    if !address.trim().starts_with("0x") || bytes.len() != 20 {
+        return Err(format!(
+            "Invalid address '{address}': expected 0x and 40 hex digits"
+        ));
+    }
+    let mut out = [0u8; 20];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
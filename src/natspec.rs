@@ -0,0 +1,38 @@
+use crate::abi::AbiItem;
+use std::collections::HashMap;
+
+/// NatSpec comments for one ABI item, merged from `userdoc` and `devdoc`.
+#[derive(Debug, Clone, Default)]
+pub struct Doc {
+    pub notice: Option<String>,
+    pub details: Option<String>,
+    /// Parameter descriptions keyed by parameter name.
+    pub params: HashMap<String, String>,
+    /// Return value descriptions keyed by name, or `_0`, `_1`, ... for
+    /// unnamed outputs.
+    pub returns: HashMap<String, String>,
+}
+
+/// The NatSpec a compiler artifact carries, if any.
+#[derive(Debug, Clone, Default)]
+pub struct NatSpec {
+    pub title: Option<String>,
+    pub notice: Option<String>,
+    pub details: Option<String>,
+    /// Item docs keyed by `doc_key`.
+    pub items: HashMap<String, Doc>,
+}
+
+impl NatSpec {
+    /// The docs recorded for `item`, matched by kind and canonical signature.
+    pub fn for_item(&self, item: &AbiItem) -> Option<&Doc> {
+        let signature = item.canonical_signature().unwrap_or_default();
+        self.items.get(&doc_key(&item.r#type, &signature))
+    }
+}
+
+/// `userdoc`/`devdoc` key their sections by signature, but functions,
+/// events and errors can share one, so the kind is part of the key.
+pub fn doc_key(kind: &str, signature: &str) -> String {
+    format!("{kind} {signature}")
+}
@@ -0,0 +1,120 @@
+use crate::abi::AbiItem;
+use crate::abi_codec::to_hex;
+use crate::codegen::{param_name, snake_case, Param, Structs, TypeShape};
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Renders named ABIs as a Python module: dicts mapping canonical
+/// signatures to function selectors, event topics and error selectors, and
+/// a dataclass per struct. With several ABIs the dict names are prefixed
+/// with the contract name.
+pub fn render_python(contracts: &[(String, Vec<AbiItem>)]) -> String {
+    let prefixed = contracts.len() > 1;
+    let mut structs = Structs::default();
+    let mut tables = Vec::new();
+
+    for (name, abi) in contracts {
+        let prefix = if prefixed {
+            format!("{}_", snake_case(name).to_ascii_uppercase())
+        } else {
+            String::new()
+        };
+
+        let mut functions = Vec::new();
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for item in abi {
+            for param in item.inputs.iter().flatten().map(Param::from) {
+                structs.shape(&param);
+            }
+            for param in item.outputs.iter().flatten().map(Param::from) {
+                structs.shape(&param);
+            }
+
+            let Some(signature) = item.canonical_signature() else {
+                continue;
+            };
+            match item.r#type.as_str() {
+                "function" => functions.extend(item.selector().map(|s| (signature, to_hex(&s)))),
+                "event" => events.extend(item.topic().map(|t| (signature, to_hex(&t)))),
+                "error" => errors.extend(item.selector().map(|s| (signature, to_hex(&s)))),
+                _ => {}
+            }
+        }
+
+        tables.push(dict(&format!("{prefix}FUNCTION_SELECTORS"), &functions));
+        tables.push(dict(&format!("{prefix}EVENT_TOPICS"), &events));
+        tables.push(dict(&format!("{prefix}ERROR_SELECTORS"), &errors));
+    }
+
+    let mut out = String::from(
+        "# Generated by abi2human. Do not edit.\n\
+         \"\"\"Selectors, event topics and struct types of a contract ABI.\"\"\"\n\n\
+         from __future__ import annotations\n",
+    );
+    if !structs.defs.is_empty() {
+        out.push_str("\nfrom dataclasses import dataclass\n");
+    }
+    out.push('\n');
+    out.push_str(&tables.join("\n\n"));
+
+    for index in 0..structs.defs.len() {
+        let fields = structs.defs[index].fields.clone();
+        let mut class = format!("@dataclass\nclass {}:", structs.defs[index].name);
+        for (i, field) in fields.iter().enumerate() {
+            class.push_str(&format!(
+                "\n    {}: {}",
+                field_name(&param_name(field, "field", i)),
+                python_type(&structs.shape(field))
+            ));
+        }
+        if fields.is_empty() {
+            class.push_str("\n    pass");
+        }
+        out.push_str("\n\n\n");
+        out.push_str(&class);
+    }
+
+    out.truncate(out.trim_end().len());
+    out
+}
+
+fn dict(name: &str, entries: &[(String, String)]) -> String {
+    if entries.is_empty() {
+        return format!("{name}: dict[str, str] = {{}}");
+    }
+    let body: String = entries
+        .iter()
+        .map(|(signature, hex)| format!("    \"{signature}\": \"{hex}\",\n"))
+        .collect();
+    format!("{name}: dict[str, str] = {{\n{body}}}")
+}
+
+fn python_type(shape: &TypeShape) -> String {
+    match shape {
+        TypeShape::Struct(name) => name.clone(),
+        TypeShape::Array(inner, _) => format!("list[{}]", python_type(inner)),
+        TypeShape::Elementary(name) => match name.as_str() {
+            "bool" => "bool",
+            "address" | "string" => "str",
+            t if t.starts_with("uint") || t.starts_with("int") => "int",
+            _ => "bytes",
+        }
+        .to_string(),
+    }
+}
+
+fn field_name(name: &str) -> String {
+    let underscore = if name.starts_with('_') { "_" } else { "" };
+    let ident = format!("{underscore}{}", snake_case(name.trim_start_matches('_')));
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
@@ -0,0 +1,247 @@
+use crate::abi::AbiItem;
+use crate::codegen::{param_name, snake_case, unique, upper_camel, Param, Structs, TypeShape};
+
+const HEADER: &str = "// Generated by abi2human. Do not edit.";
+
+const PRELUDE: &str = "\
+/// A 20-byte account address.
+pub type Address = [u8; 20];
+/// A 256-bit unsigned integer, big-endian.
+pub type U256 = [u8; 32];
+/// A 256-bit two's complement integer, big-endian.
+pub type I256 = [u8; 32];";
+
+const DERIVE: &str = "#[derive(Debug, Clone, PartialEq, Eq)]";
+
+/// Renders named ABIs as dependency-free Rust: structs for tuples, an enum
+/// of function calls, one struct per event and an enum of custom errors,
+/// each with their selectors or topics. Several ABIs are wrapped in one
+/// `mod` each.
+pub fn render_bindings(contracts: &[(String, Vec<AbiItem>)]) -> String {
+    match contracts {
+        [(name, abi)] => format!("{HEADER}\n\n{}", render_contract(name, abi)),
+        _ => {
+            let modules: Vec<String> = contracts
+                .iter()
+                .map(|(name, abi)| {
+                    let body = render_contract(name, abi)
+                        .lines()
+                        .map(|line| {
+                            if line.is_empty() {
+                                String::new()
+                            } else {
+                                format!("    {line}")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    format!("pub mod {} {{\n{body}\n}}", snake_case(name))
+                })
+                .collect();
+            format!("{HEADER}\n\n{}", modules.join("\n\n"))
+        }
+    }
+}
+
+fn render_contract(name: &str, abi: &[AbiItem]) -> String {
+    let contract = upper_camel(name);
+    let mut structs = Structs::default();
+    let mut blocks = vec![PRELUDE.to_string()];
+
+    let functions: Vec<&AbiItem> = abi.iter().filter(|i| i.r#type == "function").collect();
+    let events: Vec<&AbiItem> = abi.iter().filter(|i| i.r#type == "event").collect();
+    let errors: Vec<&AbiItem> = abi.iter().filter(|i| i.r#type == "error").collect();
+
+    let mut item_blocks = Vec::new();
+    if !functions.is_empty() {
+        item_blocks.push(render_selector_enum(
+            &format!("{contract}Call"),
+            &format!("A call to one of `{name}`'s functions."),
+            &functions,
+            &mut structs,
+        ));
+    }
+
+    let mut event_names = Vec::new();
+    for event in events {
+        let ident = unique(&mut event_names, format!("{}Event", variant_name(event)));
+        item_blocks.push(render_event(&ident, event, &mut structs));
+    }
+
+    if !errors.is_empty() {
+        item_blocks.push(render_selector_enum(
+            &format!("{contract}Error"),
+            &format!("A custom error `{name}` can revert with."),
+            &errors,
+            &mut structs,
+        ));
+    }
+
+    for index in 0..structs.defs.len() {
+        let fields = structs.defs[index].fields.clone();
+        let body = field_list(&fields, "field", &mut structs, "    pub ", ",\n");
+        blocks.push(format!(
+            "{DERIVE}\npub struct {} {{\n{body}}}",
+            structs.defs[index].name
+        ));
+    }
+
+    blocks.extend(item_blocks);
+    blocks.join("\n\n")
+}
+
+/// An enum with one variant per function or error, plus `selector()` and
+/// `signature()` accessors.
+fn render_selector_enum(
+    ident: &str,
+    doc: &str,
+    items: &[&AbiItem],
+    structs: &mut Structs,
+) -> String {
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    let mut selectors = Vec::new();
+    let mut signatures = Vec::new();
+
+    for item in items {
+        let variant = unique(&mut names, variant_name(item));
+        let signature = item.canonical_signature().unwrap_or_default();
+        let inputs: Vec<Param> = item.inputs.iter().flatten().map(Param::from).collect();
+
+        let (definition, pattern) = if inputs.is_empty() {
+            (format!("{variant},"), format!("Self::{variant}"))
+        } else {
+            let fields = field_list(&inputs, "arg", structs, "", ", ");
+            (
+                format!("{variant} {{ {} }},", fields.trim_end_matches(", ")),
+                format!("Self::{variant} {{ .. }}"),
+            )
+        };
+
+        variants.push(format!("    /// `{signature}`\n    {definition}\n"));
+        let selector = byte_array(&item.selector().unwrap_or_default());
+        selectors.push(format!("            {pattern} => {selector},\n"));
+        signatures.push(format!("            {pattern} => \"{signature}\",\n"));
+    }
+
+    let mut out = format!("/// {doc}\n{DERIVE}\npub enum {ident} {{\n");
+    out.push_str(&variants.concat());
+    out.push_str(&format!("}}\n\nimpl {ident} {{\n"));
+    out.push_str("    /// The 4-byte selector.\n");
+    out.push_str("    pub fn selector(&self) -> [u8; 4] {\n        match self {\n");
+    out.push_str(&selectors.concat());
+    out.push_str("        }\n    }\n\n");
+    out.push_str("    /// The canonical signature the selector is hashed from.\n");
+    out.push_str("    pub fn signature(&self) -> &'static str {\n        match self {\n");
+    out.push_str(&signatures.concat());
+    out.push_str("        }\n    }\n}");
+    out
+}
+
+fn render_event(ident: &str, event: &AbiItem, structs: &mut Structs) -> String {
+    let signature = event.canonical_signature().unwrap_or_default();
+    let inputs: Vec<Param> = event.inputs.iter().flatten().map(Param::from).collect();
+
+    let definition = if inputs.is_empty() {
+        format!("pub struct {ident};")
+    } else {
+        let body = field_list(&inputs, "arg", structs, "    pub ", ",\n");
+        format!("pub struct {ident} {{\n{body}}}")
+    };
+
+    let mut constants = format!("    pub const SIGNATURE: &str = \"{signature}\";\n");
+    if let Some(topic) = event.topic() {
+        constants.push_str(&format!(
+            "    /// `keccak256(SIGNATURE)`, the event's first topic.\n    \
+             pub const TOPIC0: [u8; 32] = {};\n",
+            byte_array(&topic)
+        ));
+    }
+
+    format!("/// `{event}`\n{DERIVE}\n{definition}\n\nimpl {ident} {{\n{constants}}}")
+}
+
+/// Renders `name: Type` pairs, each wrapped in `prefix` and `suffix`.
+fn field_list(
+    params: &[Param],
+    fallback: &str,
+    structs: &mut Structs,
+    prefix: &str,
+    suffix: &str,
+) -> String {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let name = field_name(&param_name(param, fallback, i));
+            let ty = rust_type(&structs.shape(param));
+            format!("{prefix}{name}: {ty}{suffix}")
+        })
+        .collect()
+}
+
+/// Spells a type shape with std types only.
+fn rust_type(shape: &TypeShape) -> String {
+    match shape {
+        TypeShape::Struct(name) => name.clone(),
+        TypeShape::Array(inner, Some(len)) => format!("[{}; {len}]", rust_type(inner)),
+        TypeShape::Array(inner, None) => format!("Vec<{}>", rust_type(inner)),
+        TypeShape::Elementary(name) => match name.as_str() {
+            "address" => "Address".to_string(),
+            "bool" => "bool".to_string(),
+            "string" => "String".to_string(),
+            "bytes" => "Vec<u8>".to_string(),
+            "function" => "[u8; 24]".to_string(),
+            other => {
+                if let Some(bits) = other.strip_prefix("uint") {
+                    integer_type('u', bits, "U256")
+                } else if let Some(bits) = other.strip_prefix("int") {
+                    integer_type('i', bits, "I256")
+                } else if let Some(len) = other.strip_prefix("bytes") {
+                    format!("[u8; {len}]")
+                } else {
+                    // Unknown types are carried as raw ABI words.
+                    "U256".to_string()
+                }
+            }
+        },
+    }
+}
+
+/// The smallest primitive that holds an `N`-bit integer, or the 256-bit
+/// word type for anything wider than 128 bits.
+fn integer_type(sign: char, bits: &str, wide: &str) -> String {
+    let bits: usize = bits.parse().unwrap_or(256);
+    match [8, 16, 32, 64, 128].into_iter().find(|&size| bits <= size) {
+        Some(size) => format!("{sign}{size}"),
+        None => wide.to_string(),
+    }
+}
+
+fn byte_array(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|b| format!("0x{b:02x}")).collect();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// The enum variant or struct name for an item: `balanceOf` becomes
+/// `BalanceOf`.
+fn variant_name(item: &AbiItem) -> String {
+    upper_camel(item.name.as_deref().unwrap_or(&item.r#type))
+}
+
+/// A snake_case field name that is a valid identifier, keeping a leading
+/// underscore from Solidity's `_to` style.
+fn field_name(name: &str) -> String {
+    let underscore = if name.starts_with('_') { "_" } else { "" };
+    let ident = format!("{underscore}{}", snake_case(name.trim_start_matches('_')));
+    match ident.as_str() {
+        "self" | "super" | "crate" | "Self" => format!("{ident}_"),
+        "as" | "async" | "await" | "break" | "const" | "continue" | "dyn" | "else" | "enum"
+        | "extern" | "false" | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match"
+        | "mod" | "move" | "mut" | "pub" | "ref" | "return" | "static" | "struct" | "trait"
+        | "true" | "type" | "unsafe" | "use" | "where" | "while" | "abstract" | "become"
+        | "box" | "do" | "final" | "gen" | "macro" | "override" | "priv" | "try" | "typeof"
+        | "unsized" | "virtual" | "yield" => format!("r#{ident}"),
+        _ => ident,
+    }
+}
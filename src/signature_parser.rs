@@ -0,0 +1,411 @@
+use crate::abi::{AbiInput, AbiItem, AbiOutput};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// Struct nesting deeper than this is treated as a recursive definition.
+const MAX_STRUCT_DEPTH: usize = 32;
+
+/// Parses human-readable ABI signatures back into full ABI items.
+///
+/// Accepts the lines `AbiItem`'s `Display` produces as well as ethers-style
+/// human-readable ABIs: `tuple(...)` and bare `(...)` tuple types, `struct`
+/// definitions referenced by name, `external`/`public` and data-location
+/// keywords. Blank lines and `//` comments are ignored. A `tuple` written
+/// without its components is an error, as its ABI and selector depend on
+/// them.
+pub fn parse_signatures<S: AsRef<str>>(lines: &[S]) -> Result<Vec<AbiItem>, String> {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.as_ref().trim().trim_end_matches(';').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .collect();
+
+    let mut structs = HashMap::new();
+    for line in &lines {
+        if let Some(rest) = strip_keyword(line, "struct") {
+            let (name, body) = rest
+                .split_once('{')
+                .ok_or_else(|| format!("Expected '{{' in struct definition: {line}"))?;
+            let body = body
+                .trim()
+                .strip_suffix('}')
+                .ok_or_else(|| format!("Expected '}}' in struct definition: {line}"))?;
+            let fields: Vec<&str> = body
+                .split(';')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .collect();
+            structs.insert(name.trim().to_string(), fields.join(", "));
+        }
+    }
+
+    let resolver = TypeResolver {
+        structs: &structs,
+        depth: Cell::new(0),
+    };
+    let mut items = Vec::new();
+    for line in lines {
+        if strip_keyword(line, "struct").is_none() {
+            let item = resolver
+                .parse_line(line)
+                .map_err(|e| format!("{e} in '{line}'"))?;
+            items.push(item);
+        }
+    }
+
+    Ok(items)
+}
+
+struct Param {
+    name: Option<String>,
+    r#type: String,
+    indexed: bool,
+    internal_type: Option<String>,
+    components: Option<Vec<Param>>,
+}
+
+impl Param {
+    fn unnamed(
+        r#type: String,
+        internal_type: Option<String>,
+        components: Option<Vec<Param>>,
+    ) -> Self {
+        Self {
+            name: None,
+            r#type,
+            indexed: false,
+            internal_type,
+            components,
+        }
+    }
+
+    fn into_input(self, with_indexed: bool) -> AbiInput {
+        AbiInput {
+            name: Some(self.name.unwrap_or_default()),
+            r#type: self.r#type,
+            indexed: with_indexed.then_some(self.indexed),
+            internal_type: self.internal_type,
+            components: self
+                .components
+                .map(|c| c.into_iter().map(|p| p.into_input(false)).collect()),
+        }
+    }
+
+    fn into_output(self) -> AbiOutput {
+        AbiOutput {
+            name: Some(self.name.unwrap_or_default()),
+            r#type: self.r#type,
+            internal_type: self.internal_type,
+            components: self
+                .components
+                .map(|c| c.into_iter().map(Param::into_output).collect()),
+        }
+    }
+}
+
+struct TypeResolver<'a> {
+    structs: &'a HashMap<String, String>,
+    depth: Cell<usize>,
+}
+
+impl TypeResolver<'_> {
+    fn parse_line(&self, line: &str) -> Result<AbiItem, String> {
+        let kind_end = line
+            .find(|c: char| c == '(' || c.is_whitespace())
+            .unwrap_or(line.len());
+        let (kind, rest) = line.split_at(kind_end);
+
+        let (name, params, modifiers) = match kind {
+            "function" | "event" | "error" => {
+                let open = rest.find('(').ok_or_else(|| "Expected '('".to_string())?;
+                let name = rest[..open].trim();
+                if name.is_empty() || !name.chars().all(is_ident_char) {
+                    return Err(format!("Invalid name '{name}'"));
+                }
+                let (params, modifiers) = split_parens(&rest[open..])?;
+                (Some(name.to_string()), params, modifiers)
+            }
+            "constructor" | "fallback" | "receive" => {
+                let (params, modifiers) = split_parens(rest.trim_start())?;
+                (None, params, modifiers)
+            }
+            _ => return Err(format!("Unknown item kind '{kind}'")),
+        };
+
+        let params = self.parse_params(params, kind == "event")?;
+        let mut mutability = None;
+        let mut anonymous = false;
+        let mut outputs = Vec::new();
+
+        let mut modifiers = modifiers.trim();
+        while !modifiers.is_empty() {
+            let word_end = modifiers
+                .find(|c: char| c == '(' || c.is_whitespace())
+                .unwrap_or(modifiers.len());
+            let (word, rest) = modifiers.split_at(word_end);
+            match word {
+                "view" | "pure" | "payable" | "nonpayable" => mutability = Some(word.to_string()),
+                "constant" => mutability = Some("view".to_string()),
+                "external" | "public" | "virtual" | "override" => {}
+                "anonymous" if kind == "event" => anonymous = true,
+                "returns" if kind == "function" => {
+                    let (params, rest) = split_parens(rest.trim_start())?;
+                    outputs = self.parse_params(params, false)?;
+                    modifiers = rest.trim();
+                    continue;
+                }
+                _ => return Err(format!("Unexpected modifier '{word}'")),
+            }
+            modifiers = rest.trim();
+        }
+
+        let inputs: Vec<AbiInput> = params
+            .into_iter()
+            .map(|p| p.into_input(kind == "event"))
+            .collect();
+
+        let item = match kind {
+            "function" => AbiItem {
+                r#type: kind.to_string(),
+                name,
+                inputs: Some(inputs),
+                outputs: Some(outputs.into_iter().map(Param::into_output).collect()),
+                state_mutability: Some(mutability.unwrap_or_else(|| "nonpayable".to_string())),
+                anonymous: None,
+                payable: None,
+                constant: None,
+            },
+            "event" => AbiItem {
+                r#type: kind.to_string(),
+                name,
+                inputs: Some(inputs),
+                outputs: None,
+                state_mutability: None,
+                anonymous: Some(anonymous),
+                payable: None,
+                constant: None,
+            },
+            "error" => AbiItem {
+                r#type: kind.to_string(),
+                name,
+                inputs: Some(inputs),
+                outputs: None,
+                state_mutability: None,
+                anonymous: None,
+                payable: None,
+                constant: None,
+            },
+            "constructor" => AbiItem {
+                r#type: kind.to_string(),
+                name: None,
+                inputs: Some(inputs),
+                outputs: None,
+                state_mutability: Some(mutability.unwrap_or_else(|| "nonpayable".to_string())),
+                anonymous: None,
+                payable: None,
+                constant: None,
+            },
+            _ => {
+                if !inputs.is_empty() {
+                    return Err(format!("{kind}() takes no parameters"));
+                }
+                let default = if kind == "receive" {
+                    "payable"
+                } else {
+                    "nonpayable"
+                };
+                AbiItem {
+                    r#type: kind.to_string(),
+                    name: None,
+                    inputs: None,
+                    outputs: None,
+                    state_mutability: Some(mutability.unwrap_or_else(|| default.to_string())),
+                    anonymous: None,
+                    payable: None,
+                    constant: None,
+                }
+            }
+        };
+
+        Ok(item)
+    }
+
+    fn parse_params(&self, list: &str, allow_indexed: bool) -> Result<Vec<Param>, String> {
+        split_top_level(list)
+            .into_iter()
+            .map(|p| self.parse_param(p, allow_indexed))
+            .collect()
+    }
+
+    fn parse_param(&self, param: &str, allow_indexed: bool) -> Result<Param, String> {
+        let param = param.trim();
+        let type_end = if param.starts_with('(') || param.starts_with("tuple(") {
+            let open = param.find('(').unwrap_or(0);
+            let close = matching_paren(param, open)?;
+            close
+                + 1
+                + param[close + 1..]
+                    .find(char::is_whitespace)
+                    .unwrap_or(param.len() - close - 1)
+        } else {
+            param.find(char::is_whitespace).unwrap_or(param.len())
+        };
+
+        let mut resolved = self.resolve_type(&param[..type_end])?;
+
+        for word in param[type_end..].split_whitespace() {
+            match word {
+                "indexed" if allow_indexed => resolved.indexed = true,
+                "memory" | "calldata" | "storage" => {}
+                "payable" if resolved.r#type == "address" => {}
+                _ if resolved.name.is_none() && word.chars().all(is_ident_char) => {
+                    resolved.name = Some(word.to_string())
+                }
+                _ => return Err(format!("Unexpected '{word}' in parameter '{param}'")),
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolves a type as written into an unnamed parameter carrying its ABI
+    /// `type`, `internalType` and tuple components.
+    fn resolve_type(&self, written: &str) -> Result<Param, String> {
+        if written.starts_with('(') || written.starts_with("tuple(") {
+            let open = written.find('(').unwrap_or(0);
+            let close = matching_paren(written, open)?;
+            let suffix = &written[close + 1..];
+            validate_array_suffix(suffix)?;
+            let components = self.parse_params(&written[open + 1..close], false)?;
+            return Ok(Param::unnamed(
+                format!("tuple{suffix}"),
+                None,
+                Some(components),
+            ));
+        }
+
+        let (base, suffix) = written.split_at(written.find('[').unwrap_or(written.len()));
+        validate_array_suffix(suffix)?;
+
+        if let Some(fields) = self.structs.get(base) {
+            if self.depth.get() >= MAX_STRUCT_DEPTH {
+                return Err(format!("Recursive struct '{base}'"));
+            }
+            self.depth.set(self.depth.get() + 1);
+            let components = self.parse_params(fields, false);
+            self.depth.set(self.depth.get() - 1);
+            let components = components?;
+            return Ok(Param::unnamed(
+                format!("tuple{suffix}"),
+                Some(format!("struct {base}{suffix}")),
+                Some(components),
+            ));
+        }
+
+        // A tuple's components are part of its type and its selector; the
+        // componentless `tuple` that `Display` writes cannot be expanded.
+        if base == "tuple" {
+            return Err(format!(
+                "Type '{written}' needs its components, e.g. tuple(address,uint256){suffix}"
+            ));
+        }
+
+        let base = match base {
+            "uint" => "uint256",
+            "int" => "int256",
+            "byte" => "bytes1",
+            other => other,
+        };
+        if !is_elementary_type(base) {
+            return Err(format!("Unknown type '{base}'"));
+        }
+
+        Ok(Param::unnamed(format!("{base}{suffix}"), None, None))
+    }
+}
+
+/// Splits `(...)rest` into the contents of the parentheses and `rest`.
+fn split_parens(s: &str) -> Result<(&str, &str), String> {
+    if !s.starts_with('(') {
+        return Err("Expected '('".to_string());
+    }
+    let close = matching_paren(s, 0)?;
+    Ok((&s[1..close], &s[close + 1..]))
+}
+
+fn matching_paren(s: &str, open: usize) -> Result<usize, String> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("Unbalanced parentheses".to_string())
+}
+
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+fn validate_array_suffix(suffix: &str) -> Result<(), String> {
+    let mut rest = suffix;
+    while !rest.is_empty() {
+        let inner = rest
+            .strip_prefix('[')
+            .and_then(|r| r.split_once(']'))
+            .ok_or_else(|| format!("Invalid array suffix '{suffix}'"))?;
+        if !inner.0.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid array suffix '{suffix}'"));
+        }
+        rest = inner.1;
+    }
+    Ok(())
+}
+
+fn is_elementary_type(t: &str) -> bool {
+    let sized = |prefix: &str, valid: &dyn Fn(u32) -> bool| {
+        t.strip_prefix(prefix)
+            .and_then(|n| n.parse::<u32>().ok())
+            .is_some_and(valid)
+    };
+
+    matches!(
+        t,
+        "address" | "bool" | "string" | "bytes" | "function" | "fixed" | "ufixed"
+    ) || sized("uint", &|n| n % 8 == 0 && (8..=256).contains(&n))
+        || sized("int", &|n| n % 8 == 0 && (8..=256).contains(&n))
+        || sized("bytes", &|n| (1..=32).contains(&n))
+        || t.starts_with("fixed")
+        || t.starts_with("ufixed")
+}
+
+fn strip_keyword<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    line.strip_prefix(keyword)
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
@@ -0,0 +1,320 @@
+use crate::abi::AbiItem;
+use crate::canonical::effective_mutability;
+use crate::codegen::{param_name, Param, Structs, TypeShape};
+
+/// Oldest compiler that accepts every construct we emit (custom errors).
+const PRAGMA: &str = "pragma solidity ^0.8.4;";
+
+/// Reserved words and type names that cannot name a parameter or field.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "address",
+    "after",
+    "alias",
+    "anonymous",
+    "apply",
+    "as",
+    "assembly",
+    "auto",
+    "bool",
+    "break",
+    "byte",
+    "bytes",
+    "calldata",
+    "case",
+    "catch",
+    "constant",
+    "constructor",
+    "continue",
+    "contract",
+    "copyof",
+    "days",
+    "default",
+    "define",
+    "delete",
+    "do",
+    "else",
+    "emit",
+    "enum",
+    "error",
+    "ether",
+    "event",
+    "external",
+    "fallback",
+    "false",
+    "final",
+    "fixed",
+    "for",
+    "function",
+    "gwei",
+    "hex",
+    "hours",
+    "if",
+    "immutable",
+    "implements",
+    "import",
+    "in",
+    "indexed",
+    "inline",
+    "interface",
+    "internal",
+    "is",
+    "let",
+    "library",
+    "macro",
+    "mapping",
+    "match",
+    "memory",
+    "minutes",
+    "modifier",
+    "mutable",
+    "new",
+    "null",
+    "of",
+    "override",
+    "partial",
+    "payable",
+    "pragma",
+    "private",
+    "promise",
+    "public",
+    "pure",
+    "receive",
+    "reference",
+    "relocatable",
+    "return",
+    "returns",
+    "sealed",
+    "seconds",
+    "sizeof",
+    "static",
+    "storage",
+    "string",
+    "struct",
+    "supports",
+    "switch",
+    "true",
+    "try",
+    "type",
+    "typedef",
+    "typeof",
+    "ufixed",
+    "unchecked",
+    "unicode",
+    "using",
+    "var",
+    "view",
+    "virtual",
+    "weeks",
+    "wei",
+    "while",
+    "years",
+];
+
+/// `name`, with `_` appended when it is a reserved word or an elementary
+/// type such as `uint8` or `bytes32`.
+fn identifier(name: &str) -> String {
+    let sized = |prefix: &str| {
+        name.strip_prefix(prefix).is_some_and(|rest| {
+            !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit() || b == b'x')
+        })
+    };
+    if KEYWORDS.contains(&name)
+        || ["int", "uint", "bytes", "fixed", "ufixed"]
+            .into_iter()
+            .any(sized)
+    {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Spells a type shape in Solidity.
+fn solidity_type(shape: &TypeShape) -> String {
+    match shape {
+        TypeShape::Elementary(name) | TypeShape::Struct(name) => name.clone(),
+        TypeShape::Array(inner, Some(len)) => format!("{}[{len}]", solidity_type(inner)),
+        TypeShape::Array(inner, None) => format!("{}[]", solidity_type(inner)),
+    }
+}
+
+fn param_list(
+    structs: &mut Structs,
+    params: &[Param],
+    location: Option<&str>,
+    indexed: &[bool],
+) -> String {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let mut out = solidity_type(&structs.shape(param));
+            if indexed.get(i) == Some(&true) {
+                out.push_str(" indexed");
+            }
+            if let Some(location) = location.filter(|_| is_reference_type(&param.r#type)) {
+                out.push(' ');
+                out.push_str(location);
+            }
+            if !param.name.is_empty() {
+                out.push(' ');
+                out.push_str(&identifier(&param.name));
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Types that need a data location when used as function parameters.
+fn is_reference_type(r#type: &str) -> bool {
+    r#type == "string" || r#type == "bytes" || r#type.ends_with(']') || r#type.starts_with("tuple")
+}
+
+/// Turns a contract or file name into an interface name such as `IToken`.
+fn interface_name(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if ident.is_empty() {
+        ident = "Contract".to_string();
+    }
+
+    let mut chars = ident.chars();
+    let already_interface =
+        chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase());
+    if already_interface {
+        ident
+    } else {
+        format!("I{ident}")
+    }
+}
+
+fn render_interface(name: &str, abi: &[AbiItem]) -> String {
+    let mut structs = Structs::default();
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+    let mut functions = Vec::new();
+    let mut special = Vec::new();
+
+    for item in abi {
+        let inputs: Vec<Param> = item.inputs.iter().flatten().map(Param::from).collect();
+        let item_name = item.name.as_deref().unwrap_or("");
+
+        match item.r#type.as_str() {
+            "event" => {
+                let indexed: Vec<bool> = item
+                    .inputs
+                    .iter()
+                    .flatten()
+                    .map(|input| input.indexed.unwrap_or(false))
+                    .collect();
+                let anonymous = if item.anonymous.unwrap_or(false) {
+                    " anonymous"
+                } else {
+                    ""
+                };
+                events.push(format!(
+                    "event {item_name}({}){anonymous};",
+                    param_list(&mut structs, &inputs, None, &indexed)
+                ));
+            }
+            "error" => {
+                errors.push(format!(
+                    "error {item_name}({});",
+                    param_list(&mut structs, &inputs, None, &[])
+                ));
+            }
+            "function" => {
+                let outputs: Vec<Param> = item.outputs.iter().flatten().map(Param::from).collect();
+                let mutability = match effective_mutability(item) {
+                    "nonpayable" => String::new(),
+                    other => format!(" {other}"),
+                };
+                let returns = if outputs.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " returns ({})",
+                        param_list(&mut structs, &outputs, Some("memory"), &[])
+                    )
+                };
+                functions.push(format!(
+                    "function {item_name}({}) external{mutability}{returns};",
+                    param_list(&mut structs, &inputs, Some("calldata"), &[])
+                ));
+            }
+            "fallback" => {
+                let payable = if effective_mutability(item) == "payable" {
+                    " payable"
+                } else {
+                    ""
+                };
+                special.push(format!("fallback() external{payable};"));
+            }
+            "receive" => special.push("receive() external payable;".to_string()),
+            // Interfaces cannot declare constructors.
+            _ => {}
+        }
+    }
+
+    let struct_blocks: Vec<String> = (0..structs.defs.len())
+        .map(|index| {
+            let fields = structs.defs[index].fields.clone();
+            let body: String = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    format!(
+                        "        {} {};\n",
+                        solidity_type(&structs.shape(field)),
+                        identifier(&param_name(field, "field", i))
+                    )
+                })
+                .collect();
+            format!("struct {} {{\n{body}    }}", structs.defs[index].name)
+        })
+        .collect();
+
+    special.extend(functions);
+    let sections: Vec<String> = [struct_blocks, events, errors, special]
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .map(|section| {
+            section
+                .iter()
+                .map(|line| format!("    {line}"))
+                .collect::<Vec<_>>()
+                .join(if section[0].starts_with("struct") {
+                    "\n\n"
+                } else {
+                    "\n"
+                })
+        })
+        .collect();
+
+    if sections.is_empty() {
+        return format!("interface {} {{}}", interface_name(name));
+    }
+    format!(
+        "interface {} {{\n{}\n}}",
+        interface_name(name),
+        sections.join("\n\n")
+    )
+}
+
+/// Renders named ABIs as a single Solidity source file with one `interface`
+/// per ABI. Struct definitions are rebuilt from tuple `components`, named
+/// after their `internalType` when the compiler recorded one.
+pub fn render_interfaces(contracts: &[(String, Vec<AbiItem>)]) -> String {
+    let interfaces: Vec<String> = contracts
+        .iter()
+        .map(|(name, abi)| render_interface(name, abi))
+        .collect();
+    format!(
+        "// SPDX-License-Identifier: UNLICENSED\n{PRAGMA}\n\n{}",
+        interfaces.join("\n\n")
+    )
+}
@@ -0,0 +1,262 @@
+use crate::abi::AbiItem;
+use crate::json_parser::{JsonParser, ParseMode};
+use std::io::{BufReader, Bytes, Read};
+use std::iter::Peekable;
+
+/// Incrementally scans a JSON document for `abi` arrays without loading the
+/// whole input into memory. Only one ABI item is buffered at a time; every
+/// other value (bytecode, sources, metadata) is skipped byte by byte.
+pub struct AbiStreamReader<R: Read> {
+    bytes: Peekable<Bytes<BufReader<R>>>,
+    path: Vec<String>,
+    found_abi: bool,
+    mode: ParseMode,
+}
+
+impl<R: Read> AbiStreamReader<R> {
+    #[cfg(test)]
+    pub fn new(reader: R) -> Self {
+        Self::with_mode(reader, ParseMode::default())
+    }
+
+    /// In strict mode every ABI item and object key is validated against
+    /// RFC 8259, and trailing content after the document is rejected.
+    /// Other values are only skipped, so malformed bytecode or metadata is
+    /// not caught: validating them would mean buffering them.
+    pub fn with_mode(reader: R, mode: ParseMode) -> Self {
+        Self {
+            bytes: BufReader::new(reader).bytes().peekable(),
+            path: Vec::new(),
+            found_abi: false,
+            mode,
+        }
+    }
+
+    /// Calls `on_item` for every ABI item as soon as it has been parsed. The
+    /// first argument is the chain of object keys leading to the `abi` array
+    /// (empty for a bare array or a top-level `abi` field), so callers can
+    /// tell contracts apart in solc standard-JSON and build-info files.
+    pub fn for_each_item<F>(&mut self, mut on_item: F) -> Result<usize, String>
+    where
+        F: FnMut(&[String], AbiItem),
+    {
+        let mut count = 0;
+
+        self.skip_whitespace()?;
+        match self.peek()? {
+            Some(b'[') => {
+                self.found_abi = true;
+                self.stream_abi_array(&mut on_item, &mut count)?;
+            }
+            Some(b'{') => self.search_object(&mut on_item, &mut count)?,
+            _ => return Err("Expected JSON array or object".to_string()),
+        }
+
+        if !self.found_abi {
+            return Err("Expected 'abi' field in object".to_string());
+        }
+
+        if self.mode == ParseMode::Strict {
+            self.skip_whitespace()?;
+            if self.peek()?.is_some() {
+                return Err("Unexpected trailing characters".to_string());
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn search_value<F>(&mut self, on_item: &mut F, count: &mut usize) -> Result<(), String>
+    where
+        F: FnMut(&[String], AbiItem),
+    {
+        self.skip_whitespace()?;
+        match self.peek()? {
+            Some(b'{') => self.search_object(on_item, count),
+            Some(b'[') => {
+                self.next_byte()?;
+                self.skip_whitespace()?;
+                if self.peek()? == Some(b']') {
+                    self.next_byte()?;
+                    return Ok(());
+                }
+                loop {
+                    self.search_value(on_item, count)?;
+                    self.skip_whitespace()?;
+                    match self.next_byte()? {
+                        Some(b',') => continue,
+                        Some(b']') => return Ok(()),
+                        _ => return Err("Expected ',' or ']' in array".to_string()),
+                    }
+                }
+            }
+            _ => self.skip_value(None),
+        }
+    }
+
+    fn search_object<F>(&mut self, on_item: &mut F, count: &mut usize) -> Result<(), String>
+    where
+        F: FnMut(&[String], AbiItem),
+    {
+        self.expect(b'{')?;
+        self.skip_whitespace()?;
+        if self.peek()? == Some(b'}') {
+            self.next_byte()?;
+            return Ok(());
+        }
+
+        loop {
+            self.skip_whitespace()?;
+            let key = self.read_key()?;
+            self.skip_whitespace()?;
+            self.expect(b':')?;
+            self.skip_whitespace()?;
+
+            if key == "abi" && self.peek()? == Some(b'[') {
+                self.found_abi = true;
+                self.stream_abi_array(on_item, count)?;
+            } else {
+                self.path.push(key);
+                let result = self.search_value(on_item, count);
+                self.path.pop();
+                result?;
+            }
+
+            self.skip_whitespace()?;
+            match self.next_byte()? {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(()),
+                _ => return Err("Expected ',' or '}' in object".to_string()),
+            }
+        }
+    }
+
+    fn stream_abi_array<F>(&mut self, on_item: &mut F, count: &mut usize) -> Result<(), String>
+    where
+        F: FnMut(&[String], AbiItem),
+    {
+        self.expect(b'[')?;
+        self.skip_whitespace()?;
+        if self.peek()? == Some(b']') {
+            self.next_byte()?;
+            return Ok(());
+        }
+
+        loop {
+            self.skip_whitespace()?;
+            let mut raw = Vec::new();
+            self.skip_value(Some(&mut raw))?;
+
+            let text = String::from_utf8(raw).map_err(|_| "Invalid UTF-8 in input".to_string())?;
+            let mut parser = JsonParser::with_mode(&text, self.mode);
+            if let Some(item) = parser.parse_abi_item()? {
+                *count += 1;
+                on_item(&self.path, item);
+            }
+
+            self.skip_whitespace()?;
+            match self.next_byte()? {
+                Some(b',') => continue,
+                Some(b']') => return Ok(()),
+                _ => return Err("Expected ',' or ']' in array".to_string()),
+            }
+        }
+    }
+
+    /// Consumes one JSON value, optionally copying its bytes into `capture`.
+    fn skip_value(&mut self, mut capture: Option<&mut Vec<u8>>) -> Result<(), String> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        loop {
+            let byte = match self.peek()? {
+                Some(b) => b,
+                None if depth == 0 && !in_string => return Ok(()),
+                None => return Err("Unexpected end of input".to_string()),
+            };
+
+            if in_string {
+                self.next_byte()?;
+                if let Some(buf) = capture.as_deref_mut() {
+                    buf.push(byte);
+                }
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    in_string = false;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                continue;
+            }
+
+            match byte {
+                b',' | b']' | b'}' if depth == 0 => return Ok(()),
+                b']' | b'}' => depth -= 1,
+                b'[' | b'{' => depth += 1,
+                b'"' => in_string = true,
+                _ => {}
+            }
+
+            self.next_byte()?;
+            if let Some(buf) = capture.as_deref_mut() {
+                buf.push(byte);
+            }
+
+            if depth == 0 && (byte == b']' || byte == b'}') {
+                return Ok(());
+            }
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, String> {
+        let mut raw = Vec::new();
+        if self.peek()? != Some(b'"') {
+            return Err("Expected string key".to_string());
+        }
+        self.skip_value(Some(&mut raw))?;
+
+        let text = String::from_utf8(raw).map_err(|_| "Invalid UTF-8 in input".to_string())?;
+        let mut parser = JsonParser::with_mode(&text, self.mode);
+        parser.parse_key()
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), String> {
+        while let Some(b) = self.peek()? {
+            if b.is_ascii_whitespace() {
+                self.next_byte()?;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, String> {
+        match self.bytes.peek() {
+            Some(Ok(b)) => Ok(Some(*b)),
+            Some(Err(e)) => Err(format!("Failed to read input: {e}")),
+            None => Ok(None),
+        }
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, String> {
+        match self.bytes.next() {
+            Some(Ok(b)) => Ok(Some(b)),
+            Some(Err(e)) => Err(format!("Failed to read input: {e}")),
+            None => Ok(None),
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        if self.next_byte()? == Some(expected) {
+            Ok(())
+        } else {
+            Err(format!("Expected '{}'", expected as char))
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::abi::{AbiInput, AbiItem, AbiOutput};
+use crate::abi_codec::to_hex;
+use crate::canonical::effective_mutability;
+use crate::json_writer::JsonValue;
+
+/// Renders an ABI as a JSON array with one object per item.
+pub fn render_structured(abi: &[AbiItem], pretty: bool) -> String {
+    JsonValue::Array(items_json(abi)).render(pretty)
+}
+
+/// Renders named ABIs as a JSON object of structured arrays.
+pub fn render_structured_sections(sections: &[(String, Vec<AbiItem>)], pretty: bool) -> String {
+    JsonValue::Object(
+        sections
+            .iter()
+            .map(|(name, abi)| (name.clone(), JsonValue::Array(items_json(abi))))
+            .collect(),
+    )
+    .render(pretty)
+}
+
+/// Renders items as JSON Lines, one object per item tagged with the
+/// contract name and, when known, the source file.
+pub fn render_lines(file: Option<&str>, contract: &str, abi: &[AbiItem]) -> String {
+    items_json(abi)
+        .into_iter()
+        .map(|item| tagged(file, contract, item).render(false))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prepends `file` and `contract` keys to an item object.
+pub fn tagged(file: Option<&str>, contract: &str, item: JsonValue) -> JsonValue {
+    let mut entries = Vec::new();
+    if let Some(file) = file {
+        entries.push(("file".to_string(), JsonValue::string(file)));
+    }
+    entries.push(("contract".to_string(), JsonValue::string(contract)));
+    if let JsonValue::Object(fields) = item {
+        entries.extend(fields);
+    }
+    JsonValue::Object(entries)
+}
+
+fn items_json(abi: &[AbiItem]) -> Vec<JsonValue> {
+    abi.iter()
+        .filter(|item| !item.r#type.is_empty() && item.r#type != "unknown")
+        .map(item_json)
+        .collect()
+}
+
+/// Describes one item: its kind and name, the human-readable and canonical
+/// signatures, its selector or event topic, mutability and parameters.
+/// Keys that do not apply to the item's kind are left out.
+pub fn item_json(item: &AbiItem) -> JsonValue {
+    let mut entries = vec![("kind".to_string(), JsonValue::string(&item.r#type))];
+    if let Some(name) = &item.name {
+        entries.push(("name".to_string(), JsonValue::string(name)));
+    }
+    entries.push((
+        "signature".to_string(),
+        JsonValue::string(&item.to_string()),
+    ));
+    if let Some(canonical) = item.canonical_signature() {
+        entries.push((
+            "canonicalSignature".to_string(),
+            JsonValue::string(&canonical),
+        ));
+    }
+    if let Some(selector) = item.selector() {
+        entries.push((
+            "selector".to_string(),
+            JsonValue::string(&to_hex(&selector)),
+        ));
+    }
+    if let Some(topic) = item.topic() {
+        entries.push(("topic".to_string(), JsonValue::string(&to_hex(&topic))));
+    }
+    match item.r#type.as_str() {
+        "event" => entries.push((
+            "anonymous".to_string(),
+            JsonValue::Bool(item.anonymous == Some(true)),
+        )),
+        "error" => {}
+        _ => entries.push((
+            "stateMutability".to_string(),
+            JsonValue::string(effective_mutability(item)),
+        )),
+    }
+
+    entries.push((
+        "inputs".to_string(),
+        JsonValue::Array(item.inputs.iter().flatten().map(input_json).collect()),
+    ));
+    if item.r#type == "function" {
+        entries.push((
+            "outputs".to_string(),
+            JsonValue::Array(item.outputs.iter().flatten().map(output_json).collect()),
+        ));
+    }
+    JsonValue::Object(entries)
+}
+
+fn input_json(input: &AbiInput) -> JsonValue {
+    let components = input
+        .components
+        .as_ref()
+        .map(|components| components.iter().map(input_json).collect());
+    param_json(
+        input.name.as_deref(),
+        &input.r#type,
+        input.canonical_type(),
+        input.internal_type.as_deref(),
+        input.indexed,
+        components,
+    )
+}
+
+fn output_json(output: &AbiOutput) -> JsonValue {
+    let components = output
+        .components
+        .as_ref()
+        .map(|components| components.iter().map(output_json).collect());
+    param_json(
+        output.name.as_deref(),
+        &output.r#type,
+        output.canonical_type(),
+        output.internal_type.as_deref(),
+        None,
+        components,
+    )
+}
+
+fn param_json(
+    name: Option<&str>,
+    r#type: &str,
+    canonical_type: String,
+    internal_type: Option<&str>,
+    indexed: Option<bool>,
+    components: Option<Vec<JsonValue>>,
+) -> JsonValue {
+    let mut entries = vec![
+        ("name".to_string(), JsonValue::string(name.unwrap_or(""))),
+        ("type".to_string(), JsonValue::string(r#type)),
+        (
+            "canonicalType".to_string(),
+            JsonValue::String(canonical_type),
+        ),
+    ];
+    if let Some(internal_type) = internal_type {
+        entries.push(("internalType".to_string(), JsonValue::string(internal_type)));
+    }
+    if let Some(indexed) = indexed {
+        entries.push(("indexed".to_string(), JsonValue::Bool(indexed)));
+    }
+    if let Some(components) = components {
+        entries.push(("components".to_string(), JsonValue::Array(components)));
+    }
+    JsonValue::Object(entries)
+}
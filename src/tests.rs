@@ -1,9 +1,41 @@
 #[cfg(test)]
 use crate::abi::{AbiInput, AbiItem, AbiOutput};
 #[cfg(test)]
-use crate::converter::Converter;
+use crate::abi_codec::{decode_values, from_hex, to_hex, ParamType};
 #[cfg(test)]
-use crate::json_parser::JsonParser;
+use crate::commands::is_command;
+#[cfg(test)]
+use crate::compact::savings_summary;
+#[cfg(test)]
+use crate::conformance::check_conformance;
+#[cfg(test)]
+use crate::converter::{Converter, OutputFormat};
+#[cfg(test)]
+use crate::dedupe::hoist_common;
+#[cfg(test)]
+use crate::diff::diff_abis;
+#[cfg(test)]
+use crate::file_ops::{combine_directory, convert_directory, convert_stream, ConvertOptions};
+#[cfg(test)]
+use crate::filter::Filter;
+#[cfg(test)]
+use crate::git::{diff_revisions, split_range};
+#[cfg(test)]
+use crate::interfaces::{detect, summary, Support};
+#[cfg(test)]
+use crate::json_parser::{JsonParser, ParseMode};
+#[cfg(test)]
+use crate::keccak::keccak256;
+#[cfg(test)]
+use crate::layout::{Layout, SortOrder};
+#[cfg(test)]
+use crate::multicall::{decode_snapshot, encode_snapshot};
+#[cfg(test)]
+use crate::natspec::NatSpec;
+#[cfg(test)]
+use crate::stream::AbiStreamReader;
+#[cfg(test)]
+use crate::ts_import::extract_exported_abis;
 
 #[test]
 fn test_parse_simple_function() {
@@ -291,3 +323,1764 @@ fn test_payable_function() {
     let formatted = item.to_string();
     assert_eq!(formatted, "function deposit() payable");
 }
+
+#[test]
+fn test_stream_finds_nested_abi_arrays() {
+    let json = r#"{
+        "input": {"sources": {"A.sol": {"content": "{\"abi\": []}"}}},
+        "output": {"contracts": {"A.sol": {
+            "A": {"abi": [{"type": "function", "name": "foo", "inputs": [], "outputs": []}],
+                  "evm": {"bytecode": {"object": "0x6080"}}},
+            "B": {"abi": [{"type": "receive", "stateMutability": "payable"}]}
+        }}}
+    }"#;
+
+    let mut seen = Vec::new();
+    let count = AbiStreamReader::new(json.as_bytes())
+        .for_each_item(|path, item| seen.push((path.last().cloned(), item.to_string())))
+        .unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(
+        seen,
+        vec![
+            (Some("A".to_string()), "function foo()".to_string()),
+            (
+                Some("B".to_string()),
+                "receive() external payable".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_convert_stream_matches_buffered_output() {
+    let json = r#"[
+        {"type": "function", "name": "transfer",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"type": "bool"}], "stateMutability": "nonpayable"},
+        "not an item",
+        {"type": "event", "name": "Paused", "inputs": []}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    let readable = Converter::convert_to_human_readable(&items);
+
+    for pretty in [true, false] {
+        let options = ConvertOptions {
+            pretty,
+            ..ConvertOptions::default()
+        };
+        let mut out = Vec::new();
+        let written = convert_stream(json.as_bytes(), &mut out, &options).unwrap();
+
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("{}\n", Converter::format_as_json_array(&readable, pretty))
+        );
+    }
+}
+
+#[test]
+fn test_convert_stream_writes_nothing_without_items() {
+    for input in [
+        "{",
+        r#"{"name": "Token"}"#,
+        "[]",
+        r#"[{"type": "unknown"}]"#,
+    ] {
+        for format in [
+            OutputFormat::Json,
+            OutputFormat::Structured,
+            OutputFormat::Raw,
+        ] {
+            let options = ConvertOptions {
+                format,
+                ..ConvertOptions::default()
+            };
+            let mut out = Vec::new();
+            assert!(convert_stream(input.as_bytes(), &mut out, &options).is_err());
+            assert!(
+                out.is_empty(),
+                "{input} wrote {:?}",
+                String::from_utf8_lossy(&out)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parse_string_escapes() {
+    let json = r#"[{"type": "function", "name": "a\/b\b\fé🚀", "inputs": []}]"#;
+
+    let items = JsonParser::new(json).parse_abi().unwrap();
+    assert_eq!(items[0].name.as_deref(), Some("a/b\u{8}\u{c}é🚀"));
+
+    // Characters outside the BMP arrive as an escaped surrogate pair.
+    let json = r#"[{"type": "function", "name": "smile\ud83d\ude00\u00e9", "inputs": []}]"#;
+    let items = JsonParser::new(json).parse_abi().unwrap();
+    assert_eq!(items[0].name.as_deref(), Some("smile😀é"));
+
+    // A surrogate that is not part of a pair has no character to decode to.
+    for lone in [r#"\ud83d"#, r#"\ud83dx"#, r#"\ud83d\u0041"#, r#"\ude00"#] {
+        let json = format!(r#"[{{"type": "function", "name": "{lone}", "inputs": []}}]"#);
+        assert!(JsonParser::new(&json).parse_abi().is_err(), "{lone}");
+    }
+}
+
+#[test]
+fn test_parse_string_rejects_malformed_escapes() {
+    for json in [
+        r#"[{"type": "function", "name": "bad\q"}]"#,
+        r#"[{"type": "function", "name": "\ud83d"}]"#,
+        r#"[{"type": "function", "name": "\ude80\ud83d"}]"#,
+        r#"[{"type": "function", "name": "\u12"}]"#,
+        "[{\"type\": \"function\", \"name\": \"tab\there\"}]",
+    ] {
+        assert!(JsonParser::new(json).parse_abi().is_err(), "{json}");
+    }
+
+    let err = JsonParser::new(r#"["\q"]"#).parse_abi().unwrap_err();
+    assert_eq!(err, "Invalid escape sequence '\\q' at position 3");
+}
+
+#[test]
+fn test_strict_mode_rejects_non_conforming_json() {
+    let cases = [
+        (
+            r#"[{"type": "event", "x": 1-2e+}]"#,
+            "Expected ',' or '}' in object at position 25",
+        ),
+        (
+            r#"[{"type": "event", "x": --5}]"#,
+            "Expected digit in number at position 25",
+        ),
+        (
+            r#"[{"type": "event", "x": 012}]"#,
+            "Leading zeros are not allowed in numbers at position 25",
+        ),
+        (
+            r#"[{"type": "event", "x": 1.}]"#,
+            "Expected digit after decimal point at position 26",
+        ),
+        (
+            r#"[{"type": "event", "type": "function"}]"#,
+            "Duplicate key 'type' at position 19",
+        ),
+        (
+            r#"[{"type": "event"}] trailing"#,
+            "Unexpected trailing characters at position 20",
+        ),
+        (
+            "[{\"type\":\u{a0}\"event\"}]",
+            "Unexpected character at position 9",
+        ),
+    ];
+
+    for (json, expected) in cases {
+        let err = JsonParser::with_mode(json, ParseMode::Strict)
+            .parse_abi()
+            .unwrap_err();
+        assert_eq!(err, expected, "{json}");
+    }
+}
+
+#[test]
+fn test_standard_mode_tolerates_sloppy_json() {
+    let json = r#"[{"type": "event", "name": "A", "type": "function", "inputs": []}] // trailing"#;
+
+    let items = JsonParser::new(json).parse_abi().unwrap();
+    assert_eq!(items[0].to_string(), "function A()");
+
+    let strict = r#"[{"type": "function", "name": "f", "gas": -1.5e3, "inputs": []}]"#;
+    assert!(JsonParser::with_mode(strict, ParseMode::Strict)
+        .parse_abi()
+        .is_ok());
+}
+
+#[test]
+fn test_json5_mode_accepts_typescript_snippets() {
+    let snippet = r#"[
+        // ERC20 transfer
+        {
+            type: 'function',
+            name: 'transfer',
+            /* arguments */
+            inputs: [
+                { name: "to", type: 'address' },
+                { name: 'amount', type: 'uint256', },
+            ],
+            outputs: [{ type: 'bool' }],
+            stateMutability: 'nonpayable',
+        },
+        { type: 'event', name: 'it\'s', inputs: [] },
+    ] as const"#;
+
+    let items = JsonParser::with_mode(snippet, ParseMode::Json5)
+        .parse_abi()
+        .unwrap();
+    let readable = Converter::convert_to_human_readable(&items);
+    assert_eq!(
+        readable,
+        vec![
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "event it's()",
+        ]
+    );
+
+    assert!(JsonParser::new(snippet).parse_abi().is_err());
+
+    for unterminated in ["[] /* unterminated", "[/* unterminated ]", "[] /*/"] {
+        let error = JsonParser::with_mode(unterminated, ParseMode::Json5)
+            .parse_abi()
+            .unwrap_err();
+        assert!(error.starts_with("Unterminated comment"), "{error}");
+    }
+}
+
+#[test]
+fn test_extract_exported_abis_from_typescript() {
+    let source = r#"
+        import type { Abi } from 'viem'
+
+        // export const commented = [{ type: 'function', name: 'nope' }]
+        const internal = [{ type: 'function', name: 'hidden', inputs: [] }]
+        export const addresses = ['0x01', '0x02'] as const
+        export const chains = [mainnet, sepolia]
+        export const label = "export const fake = [{}]"
+
+        export const erc20Abi = [
+          { type: 'function', name: 'totalSupply', inputs: [], outputs: [{ type: 'uint256' }], stateMutability: 'view' },
+        ] as const
+
+        export const vaultAbi: Abi = [
+          { "type": "receive", "stateMutability": "payable" },
+        ] as const satisfies Abi
+    "#;
+
+    let exports = extract_exported_abis(source).unwrap();
+    let names: Vec<&str> = exports.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["erc20Abi", "vaultAbi"]);
+    assert_eq!(
+        exports[0].1[0].to_string(),
+        "function totalSupply() view returns (uint256)"
+    );
+    assert_eq!(exports[1].1[0].to_string(), "receive() external payable");
+
+    let error = extract_exported_abis("export const chains = [mainnet, sepolia]").unwrap_err();
+    assert!(
+        error.starts_with("Failed to parse export 'chains'"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_convert_directory_script_modules() {
+    let dir = std::env::temp_dir().join(format!("abi2human-scripts-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(
+        dir.join("src/Token.json"),
+        r#"[{"type": "function", "name": "totalSupply", "stateMutability": "view",
+            "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("src/abis.ts"),
+        "export const vaultAbi = [{ type: 'receive', stateMutability: 'payable' }] as const",
+    )
+    .unwrap();
+    std::fs::write(dir.join("src/app.ts"), "export const answer = 42").unwrap();
+
+    let converted = |pattern: Option<&str>| {
+        let options = ConvertOptions {
+            pattern: pattern.map(str::to_string),
+            ..ConvertOptions::default()
+        };
+        let mut inputs: Vec<String> =
+            convert_directory(&dir.join("src"), &dir.join("out"), &options)
+                .iter()
+                .map(|r| {
+                    assert!(r.success, "{:?}", r.error);
+                    r.input_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+        inputs.sort();
+        inputs
+    };
+    // Without a pattern only JSON files are read, so frontend sources such
+    // as app.ts are not mistaken for ABIs.
+    let default = converted(None);
+    let modules = converted(Some("abis.*"));
+    let module_output = std::fs::read_to_string(dir.join("out/abis.json"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(default, ["Token.json"]);
+    assert_eq!(modules, ["abis.ts"]);
+    assert!(module_output
+        .unwrap()
+        .contains("receive() external payable"));
+}
+
+#[test]
+fn test_format_json_object() {
+    let sections = vec![
+        ("erc20Abi".to_string(), vec!["function a()".to_string()]),
+        ("empty".to_string(), vec![]),
+    ];
+
+    assert_eq!(
+        Converter::format_as_json_object(&sections, false),
+        r#"{"erc20Abi":["function a()"],"empty":[]}"#
+    );
+    assert_eq!(
+        Converter::format_as_json_object(&sections, true),
+        "{\n  \"erc20Abi\": [\n    \"function a()\"\n  ],\n  \"empty\": []\n}"
+    );
+}
+
+#[test]
+fn test_parse_human_readable_round_trips_display() {
+    let lines = [
+        "constructor(address admin, uint256 fee) payable",
+        "function transfer(address to, uint256 amount) returns (bool)",
+        "function balanceOf(address account) view returns (uint256)",
+        "function deposit() payable",
+        "event Transfer(address indexed from, address indexed to, uint256 value)",
+        "event Raw(bytes32 data) anonymous",
+        "error InsufficientBalance(uint256 available, uint256 required)",
+        "fallback() external payable",
+        "receive() external payable",
+    ];
+
+    let items = Converter::parse_human_readable(&lines.join("\n")).unwrap();
+    assert_eq!(Converter::convert_to_human_readable(&items), lines);
+
+    let json = Converter::format_as_abi_json(&items, false);
+    let reparsed = Converter::parse_abi_content(&json).unwrap();
+    assert_eq!(Converter::convert_to_human_readable(&reparsed), lines);
+}
+
+#[test]
+fn test_parse_human_readable_tuples_and_structs() {
+    let content = r#"[
+        "struct Leg { address token; uint amount; }",
+        "function swap(tuple(address tokenIn, uint256 amountIn)[] calldata legs, Leg memory last) external returns (uint256 out)",
+        "event Indexed(address payable indexed who)"
+    ]"#;
+
+    let items = Converter::parse_human_readable(content).unwrap();
+    assert_eq!(
+        Converter::format_as_abi_json(&items[..1], false),
+        concat!(
+            r#"[{"type":"function","name":"swap","inputs":["#,
+            r#"{"name":"legs","type":"tuple[]","components":[{"name":"tokenIn","type":"address"},{"name":"amountIn","type":"uint256"}]},"#,
+            r#"{"name":"last","type":"tuple","internalType":"struct Leg","components":[{"name":"token","type":"address"},{"name":"amount","type":"uint256"}]}],"#,
+            r#""outputs":[{"name":"out","type":"uint256"}],"stateMutability":"nonpayable"}]"#
+        )
+    );
+    assert_eq!(items[1].to_string(), "event Indexed(address indexed who)");
+
+    assert!(Converter::parse_human_readable("function f(Unknown x)").is_err());
+    assert!(Converter::parse_human_readable("struct A { A inner; }\nfunction f(A a)").is_err());
+    for bare in [
+        "function swap(tuple params) returns (uint256)",
+        "function swap(tuple[] params)",
+        "event Swapped(tuple indexed params)",
+    ] {
+        let error = Converter::parse_human_readable(bare).unwrap_err();
+        assert!(error.contains("needs its components"), "{error}");
+    }
+}
+
+#[test]
+fn test_keccak_and_selectors() {
+    assert_eq!(
+        to_hex(&keccak256(b"")),
+        "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+
+    let items = Converter::parse_human_readable(
+        "function transfer(address to, uint256 amount) returns (bool)\n\
+         function swap(tuple(address token, uint256 amount)[] legs)\n\
+         error InsufficientBalance(uint256 available, uint256 required)",
+    )
+    .unwrap();
+    assert_eq!(to_hex(&items[0].selector().unwrap()), "0xa9059cbb");
+    assert_eq!(
+        items[1].canonical_signature().unwrap(),
+        "swap((address,uint256)[])"
+    );
+    assert_eq!(
+        items[2].canonical_signature().unwrap(),
+        "InsufficientBalance(uint256,uint256)"
+    );
+}
+
+#[test]
+fn test_decode_values() {
+    let word = |hex: &str| format!("{hex:0>64}");
+    let data = from_hex(
+        &[
+            "f".repeat(64),
+            word("60"),
+            word("1"),
+            word("3"),
+            format!("{:0<64}", "616263"),
+        ]
+        .concat(),
+    )
+    .unwrap();
+
+    let types = vec![
+        ParamType::Int,
+        ParamType::String,
+        ParamType::FixedArray(Box::new(ParamType::Bool), 1),
+    ];
+    assert_eq!(
+        decode_values(&types, &data).unwrap(),
+        vec!["-1", "abc", "[true]"]
+    );
+}
+
+#[test]
+fn test_multicall_snapshot_round_trip() {
+    let abi = Converter::parse_abi_content(
+        r#"[
+        {"type": "function", "name": "owner", "inputs": [], "outputs": [{"name": "", "type": "address"}], "stateMutability": "view"},
+        {"type": "function", "name": "balanceOf", "inputs": [{"name": "a", "type": "address"}], "outputs": [{"type": "uint256"}], "stateMutability": "view"},
+        {"type": "function", "name": "totalSupply", "inputs": [], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view"},
+        {"type": "function", "name": "mint", "inputs": [], "outputs": [], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "name", "inputs": [], "outputs": [{"type": "string"}], "constant": true}
+    ]"#,
+    )
+    .unwrap();
+
+    let calldata = encode_snapshot(&abi, "0x6B175474E89094C44Da98b954EedeAC495271d0F").unwrap();
+    assert_eq!(&calldata[..4], &[0x82, 0xad, 0x56, 0xcb]);
+    assert_eq!(calldata.len(), 4 + 32 * 2 + 3 * 32 + 3 * 160);
+    assert_eq!(
+        &calldata[4 + 32 * 5 + 128..4 + 32 * 5 + 132],
+        &[0x8d, 0xa5, 0xcb, 0x5b]
+    );
+
+    let word = |hex: &str| format!("{hex:0>64}");
+    let result = [
+        word("20"),
+        word("3"),
+        word("60"),
+        word("e0"),
+        word("160"),
+        // owner(): success, 32 bytes
+        word("1"),
+        word("40"),
+        word("20"),
+        word("6b175474e89094c44da98b954eedeac495271d0f"),
+        // totalSupply(): success, 32 bytes
+        word("1"),
+        word("40"),
+        word("20"),
+        word("de0b6b3a7640000"),
+        // name(): reverted with no data
+        word("0"),
+        word("40"),
+        word("0"),
+    ]
+    .concat();
+
+    let entries = decode_snapshot(&abi, &from_hex(&result).unwrap()).unwrap();
+    let summary: Vec<String> = entries
+        .iter()
+        .map(|e| format!("{} {} {}", e.name, e.success, e.values[0].1))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "owner true 0x6b175474e89094c44da98b954eedeac495271d0f",
+            "totalSupply true 1000000000000000000",
+            "name false 0x",
+        ]
+    );
+
+    assert!(encode_snapshot(&abi, "0x1234").is_err());
+}
+
+#[test]
+fn test_canonical_format_normalises_legacy_abi() {
+    let json = r#"[
+        {"type": "event", "name": "Transfer", "inputs": [{"name": "from", "type": "address", "indexed": true}, {"type": "uint256"}]},
+        {"constant": true, "inputs": [], "name": "name", "outputs": [{"name": "", "type": "string"}], "payable": false, "type": "function"},
+        {"constant": false, "inputs": [], "name": "deposit", "outputs": [], "payable": true, "type": "function"},
+        {"type": "error", "name": "Bad"},
+        {"inputs": [], "payable": false, "type": "constructor"}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    assert_eq!(
+        Converter::format_items(
+            "Token",
+            &items,
+            &NatSpec::default(),
+            OutputFormat::Canonical,
+            false
+        ),
+        concat!(
+            r#"[{"type":"constructor","inputs":[],"stateMutability":"nonpayable"},"#,
+            r#"{"type":"function","name":"deposit","inputs":[],"outputs":[],"stateMutability":"payable"},"#,
+            r#"{"type":"function","name":"name","inputs":[],"outputs":[{"name":"","type":"string"}],"stateMutability":"view"},"#,
+            r#"{"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true},{"name":"","type":"uint256","indexed":false}],"anonymous":false},"#,
+            r#"{"type":"error","name":"Bad","inputs":[]}]"#
+        )
+    );
+
+    let canonical = Converter::format_items(
+        "Token",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Canonical,
+        true,
+    );
+    let reparsed = Converter::parse_abi_content(&canonical).unwrap();
+    assert_eq!(
+        Converter::format_items(
+            "Token",
+            &reparsed,
+            &NatSpec::default(),
+            OutputFormat::Canonical,
+            true
+        ),
+        canonical
+    );
+}
+
+#[test]
+fn test_output_format_parse() {
+    assert_eq!(OutputFormat::parse("raw"), Ok(OutputFormat::Raw));
+    assert_eq!(
+        OutputFormat::parse("canonical"),
+        Ok(OutputFormat::Canonical)
+    );
+    assert_eq!(OutputFormat::Canonical.extension(), "json");
+    assert!(OutputFormat::parse("yaml").is_err());
+}
+
+#[test]
+fn test_solidity_interface_output() {
+    let json = r#"[
+        {"type": "constructor", "inputs": [{"name": "owner", "type": "address"}]},
+        {"type": "function", "name": "submit", "stateMutability": "payable",
+         "inputs": [{"name": "order", "type": "tuple", "internalType": "struct Exchange.Order", "components": [
+             {"name": "maker", "type": "address"},
+             {"name": "legs", "type": "tuple[]", "internalType": "struct Exchange.Leg[]", "components": [
+                 {"name": "token", "type": "address"}, {"name": "amount", "type": "uint256"}]}]},
+             {"name": "sig", "type": "bytes"}],
+         "outputs": [{"name": "", "type": "bytes32"}]},
+        {"constant": true, "inputs": [], "name": "name", "outputs": [{"name": "", "type": "string"}], "type": "function"},
+        {"type": "event", "name": "Filled", "inputs": [{"name": "id", "type": "bytes32", "indexed": true}, {"name": "", "type": "uint256", "indexed": false}], "anonymous": false},
+        {"type": "error", "name": "Expired", "inputs": [{"name": "at", "type": "uint256"}]},
+        {"type": "receive", "stateMutability": "payable"}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    let expected = "\
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.4;
+
+interface IExchange {
+    struct Order {
+        address maker;
+        Leg[] legs;
+    }
+
+    struct Leg {
+        address token;
+        uint256 amount;
+    }
+
+    event Filled(bytes32 indexed id, uint256);
+
+    error Expired(uint256 at);
+
+    receive() external payable;
+    function submit(Order calldata order, bytes calldata sig) external payable returns (bytes32);
+    function name() external view returns (string memory);
+}";
+    assert_eq!(
+        Converter::format_items(
+            "Exchange",
+            &items,
+            &NatSpec::default(),
+            OutputFormat::Solidity,
+            true
+        ),
+        expected
+    );
+
+    // Reserved parameter names get a `_` suffix, and structs that share a
+    // name across libraries keep their qualifier.
+    let json = r#"[
+        {"type": "function", "name": "set", "stateMutability": "nonpayable", "outputs": [],
+         "inputs": [
+             {"name": "type", "type": "bytes"},
+             {"name": "a", "type": "tuple", "internalType": "struct A.Info", "components": [
+                 {"name": "from", "type": "address"}, {"name": "uint8", "type": "uint8"}]},
+             {"name": "b", "type": "tuple", "internalType": "struct B.Info", "components": [
+                 {"name": "storage", "type": "bool"}]}]}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let interface = Converter::format_items(
+        "Registry",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Solidity,
+        true,
+    );
+    for expected in [
+        "    struct Info {\n        address from;\n        uint8 uint8_;\n    }",
+        "    struct B_Info {\n        bool storage_;\n    }",
+        "function set(bytes calldata type_, Info calldata a, B_Info calldata b) external;",
+    ] {
+        assert!(
+            interface.contains(expected),
+            "missing {expected:?} in:\n{interface}"
+        );
+    }
+}
+
+#[test]
+fn test_markdown_output_with_natspec() {
+    let artifact = r#"{
+        "abi": [
+            {"type": "function", "name": "balanceOf", "stateMutability": "view",
+             "inputs": [{"name": "owner", "type": "address"}],
+             "outputs": [{"name": "", "type": "uint256"}]},
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+             "outputs": [{"name": "", "type": "bool"}]},
+            {"type": "event", "name": "Transfer", "anonymous": false,
+             "inputs": [{"name": "from", "type": "address", "indexed": true}]}
+        ],
+        "metadata": {"output": {
+            "userdoc": {"notice": "A token", "methods": {"transfer(address,uint256)": {"notice": "Moves tokens"}}},
+            "devdoc": {"title": "Token", "methods": {"transfer(address,uint256)": {
+                "params": {"to": "Recipient | owner"}, "returns": {"_0": "Always true"}}}}
+        }}
+    }"#;
+
+    let items = Converter::parse_abi_content(artifact).unwrap();
+    let docs = Converter::parse_natspec(artifact, ParseMode::Standard, OutputFormat::Markdown);
+    let skipped = Converter::parse_natspec(artifact, ParseMode::Standard, OutputFormat::Json);
+    assert!(skipped.title.is_none() && skipped.notice.is_none());
+    let markdown = Converter::format_items("Token", &items, &docs, OutputFormat::Markdown, true);
+
+    let expected = "\
+# Token
+
+Token
+
+A token
+
+## Read Functions
+
+### balanceOf
+
+```solidity
+function balanceOf(address owner) view returns (uint256)
+```
+
+| Parameter | Type |
+| --- | --- |
+| `owner` | `address` |
+
+| Returns | Type |
+| --- | --- |
+| - | `uint256` |
+
+## Write Functions
+
+### transfer
+
+```solidity
+function transfer(address to, uint256 amount) returns (bool)
+```
+
+Moves tokens
+
+| Parameter | Type | Description |
+| --- | --- | --- |
+| `to` | `address` | Recipient \\| owner |
+| `amount` | `uint256` |  |
+
+| Returns | Type | Description |
+| --- | --- | --- |
+| - | `bool` | Always true |
+
+## Events
+
+### Transfer
+
+```solidity
+event Transfer(address indexed from)
+```
+
+| Parameter | Type |
+| --- | --- |
+| `from` | `address` indexed |";
+    assert_eq!(markdown, expected);
+}
+
+#[test]
+fn test_html_report_output() {
+    let json = r#"[
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "to", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}]}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    let html = Converter::format_items(
+        "Token<&>",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Html,
+        true,
+    );
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>"));
+    assert!(!html.contains("<script"));
+    assert!(html.contains("<title>Token&lt;&amp;&gt;</title>"));
+    assert!(html.contains(
+        "<tr id=\"Token&lt;&amp;&gt;-transfer(address,uint256)\"><td class=\"selector\">\
+         <a href=\"#Token&lt;&amp;&gt;-transfer(address,uint256)\"><code>0xa9059cbb</code></a>"
+    ));
+    assert!(html.contains(
+        "<code>0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef</code>"
+    ));
+    assert!(html.contains("<summary>Events (1)</summary>"));
+}
+
+#[test]
+fn test_html_report_directory() {
+    let dir = std::env::temp_dir().join(format!("abi2human-report-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("out/Token.sol")).unwrap();
+    std::fs::create_dir_all(dir.join("out/Wrapped.sol")).unwrap();
+    std::fs::create_dir_all(dir.join("empty")).unwrap();
+    std::fs::write(
+        dir.join("out/Token.sol/Token.json"),
+        r#"{"abi": [{"type": "function", "name": "totalSupply", "stateMutability": "view",
+            "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("out/Wrapped.sol/Token.json"),
+        r#"{"abi": [{"type": "function", "name": "totalSupply", "stateMutability": "view",
+            "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]}"#,
+    )
+    .unwrap();
+
+    let options = ConvertOptions {
+        format: OutputFormat::Html,
+        ..ConvertOptions::default()
+    };
+    let results = convert_directory(&dir.join("out"), &dir.join("report"), &options);
+    let report = std::fs::read_to_string(dir.join("report/index.html"));
+    let empty = convert_directory(&dir.join("empty"), &dir.join("report2"), &options);
+    let empty_report = dir.join("report2/index.html").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.success));
+    let report = report.unwrap();
+    assert!(report.contains("<code>0x18160ddd</code>"));
+    for id in ["Token", "Token1"] {
+        assert_eq!(report.matches(&format!("<section id=\"{id}\">")).count(), 1);
+        assert_eq!(
+            report
+                .matches(&format!("id=\"{id}-totalSupply()\""))
+                .count(),
+            1
+        );
+    }
+
+    assert_eq!(empty.len(), 1);
+    assert!(!empty[0].success);
+    assert!(!empty_report);
+}
+
+#[test]
+fn test_typescript_module_output() {
+    let json = r#"[
+        {"constant": true, "inputs": [], "name": "decimals", "outputs": [{"name": "", "type": "uint8"}], "payable": false, "type": "function"},
+        {"type": "event", "name": "Transfer", "inputs": [{"name": "from", "type": "address", "indexed": true, "internalType": "address"}]}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    let module = Converter::format_items(
+        "ERC20Permit",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::TypeScript,
+        false,
+    );
+
+    assert_eq!(
+        module,
+        concat!(
+            "// Generated by abi2human. Do not edit.\n\n",
+            r#"export const erc20PermitAbi = [{"type":"function","name":"decimals","inputs":[],"outputs":[{"name":"","type":"uint8"}],"stateMutability":"view"},"#,
+            r#"{"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true,"internalType":"address"}],"anonymous":false}] as const;"#,
+            "\n\nexport type Erc20PermitAbi = typeof erc20PermitAbi;"
+        )
+    );
+
+    let sections = vec![
+        ("USDCToken".to_string(), items.clone()),
+        ("my-vault".to_string(), items),
+    ];
+    let module = Converter::format_sections(&sections, OutputFormat::TypeScript, true);
+    assert!(module.contains("export const usdcTokenAbi = ["));
+    assert!(module.contains("export type MyVaultAbi = typeof myVaultAbi;"));
+}
+
+#[test]
+fn test_rust_bindings_output() {
+    let json = r#"[
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "function", "name": "DOMAIN_SEPARATOR", "inputs": [], "outputs": [{"name": "", "type": "bytes32"}], "stateMutability": "view"},
+        {"type": "function", "name": "submit", "stateMutability": "payable", "outputs": [],
+         "inputs": [{"name": "order", "type": "tuple", "internalType": "struct Exchange.Order", "components": [
+             {"name": "type", "type": "uint8"},
+             {"name": "legs", "type": "tuple[2][]", "internalType": "struct Exchange.Leg[2][]",
+              "components": [{"name": "", "type": "int128"}]}]}]},
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "to", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}]},
+        {"type": "error", "name": "Unauthorized", "inputs": []}
+    ]"#;
+
+    let items = Converter::parse_abi_content(json).unwrap();
+    let rust = Converter::format_items(
+        "ERC20Token",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Rust,
+        true,
+    );
+
+    for expected in [
+        "pub struct Order {\n    pub r#type: u8,\n    pub legs: Vec<[Leg; 2]>,\n}",
+        "pub struct Leg {\n    pub field0: i128,\n}",
+        "pub enum Erc20TokenCall {",
+        "    Transfer { to: Address, amount: U256 },",
+        "    DomainSeparator,",
+        "            Self::Transfer { .. } => [0xa9, 0x05, 0x9c, 0xbb],",
+        "            Self::DomainSeparator => \"DOMAIN_SEPARATOR()\",",
+        "pub struct TransferEvent {",
+        "    pub const TOPIC0: [u8; 32] = [0xdd, 0xf2, 0x52, 0xad,",
+        "pub enum Erc20TokenError {",
+        "            Self::Unauthorized => [0x82, 0xb4, 0x29, 0x00],",
+    ] {
+        assert!(rust.contains(expected), "missing {expected:?} in:\n{rust}");
+    }
+}
+
+#[test]
+fn test_python_and_go_output() {
+    let json = r#"[
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "function", "name": "submit", "stateMutability": "nonpayable", "outputs": [],
+         "inputs": [{"name": "order", "type": "tuple", "internalType": "struct Exchange.Order", "components": [
+             {"name": "from", "type": "address"}, {"name": "amounts", "type": "uint128[]"},
+             {"name": "fee", "type": "uint24"}, {"name": "nonce", "type": "uint64"},
+             {"name": "tick", "type": "int8"}]}]},
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "to", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}]},
+        {"type": "error", "name": "Unauthorized", "inputs": []}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let docs = NatSpec::default();
+
+    let python = Converter::format_items("Token", &items, &docs, OutputFormat::Python, true);
+    for expected in [
+        "FUNCTION_SELECTORS: dict[str, str] = {\n    \"transfer(address,uint256)\": \"0xa9059cbb\",",
+        "\"Transfer(address,address,uint256)\": \"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef\",",
+        "ERROR_SELECTORS: dict[str, str] = {\n    \"Unauthorized()\": \"0x82b42900\",\n}",
+        "@dataclass\nclass Order:\n    from_: str\n    amounts: list[int]",
+    ] {
+        assert!(python.contains(expected), "missing {expected:?} in:\n{python}");
+    }
+
+    let go = Converter::format_items("Token", &items, &docs, OutputFormat::Go, true);
+    for expected in [
+        "package token\n",
+        "\tTransferSelector          = \"0xa9059cbb\"",
+        "\tUnauthorizedErrorSelector = \"0x82b42900\"",
+        "type Order struct {\n\tFrom    [20]byte   `abi:\"from\"`\n\tAmounts []*big.Int `abi:\"amounts\"`\n\
+         \tFee     *big.Int   `abi:\"fee\"`\n\tNonce   uint64     `abi:\"nonce\"`\n\
+         \tTick    int8       `abi:\"tick\"`\n}",
+        "import \"math/big\"",
+    ] {
+        assert!(go.contains(expected), "missing {expected:?} in:\n{go}");
+    }
+}
+
+#[test]
+fn test_compact_output() {
+    let json = r#"[
+        {"type": "constructor", "inputs": [{"name": "owner", "type": "address"}], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "function", "name": "getReserves", "stateMutability": "view", "inputs": [],
+         "outputs": [{"name": "reserve0", "type": "uint112"}, {"name": "reserve1", "type": "uint112"}]},
+        {"type": "receive", "stateMutability": "payable"},
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}]},
+        {"type": "error", "name": "Unauthorized", "inputs": []}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let docs = NatSpec::default();
+
+    assert_eq!(
+        Converter::format_items(
+            "Pair",
+            &items,
+            &docs,
+            OutputFormat::Compact { names: true },
+            true
+        ),
+        "## constructor\n\
+         constructor(address owner)\n\
+         ## view\n\
+         getReserves()→(uint112 reserve0,uint112 reserve1)\n\
+         ## write\n\
+         transfer(address to,uint256 amount)→bool\n\
+         ## payable\n\
+         receive()\n\
+         ## event\n\
+         Transfer(address indexed from,uint256 value)\n\
+         ## error\n\
+         Unauthorized()"
+    );
+
+    let unnamed = Converter::format_items(
+        "Pair",
+        &items,
+        &docs,
+        OutputFormat::Compact { names: false },
+        true,
+    );
+    assert!(unnamed.contains("getReserves()→(uint112,uint112)"));
+    assert!(unnamed.contains("Transfer(address indexed,uint256)"));
+
+    assert_eq!(
+        savings_summary(1000, 250),
+        "📉 1000 → 250 bytes (75% smaller, ~250 → ~63 tokens)"
+    );
+}
+
+#[test]
+fn test_group_and_sort_output() {
+    let json = r#"[
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}], "outputs": []},
+        {"type": "function", "name": "balanceOf", "stateMutability": "view",
+         "inputs": [{"name": "a", "type": "address"}], "outputs": [{"name": "", "type": "uint256"}]},
+        {"type": "constructor", "inputs": [], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "approve", "stateMutability": "nonpayable",
+         "inputs": [], "outputs": []},
+        {"type": "event", "name": "Transfer", "inputs": [], "anonymous": false},
+        {"type": "error", "name": "Bad", "inputs": []}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let docs = NatSpec::default();
+    let grouped = Layout {
+        group: true,
+        sort: SortOrder::Name,
+    };
+
+    assert_eq!(
+        Converter::format_contract(
+            "T",
+            &items,
+            &docs,
+            OutputFormat::Raw,
+            true,
+            grouped,
+            &Filter::default()
+        ),
+        "// constructor\n\
+         constructor()\n\
+         // view/pure\n\
+         function balanceOf(address a) view returns (uint256)\n\
+         // nonpayable\n\
+         function approve()\n\
+         function transfer(address to)\n\
+         // events\n\
+         event Transfer()\n\
+         // errors\n\
+         error Bad()"
+    );
+    assert_eq!(
+        Converter::format_contract(
+            "T",
+            &items,
+            &docs,
+            OutputFormat::Json,
+            false,
+            grouped,
+            &Filter::default()
+        ),
+        r#"{"constructor":["constructor()"],"view/pure":["function balanceOf(address a) view returns (uint256)"],"nonpayable":["function approve()","function transfer(address to)"],"events":["event Transfer()"],"errors":["error Bad()"]}"#
+    );
+
+    // approve() = 0x12424e3f, balanceOf(address) = 0x70a08231,
+    // transfer(address) = 0x1a695230
+    let by_selector = Layout {
+        group: false,
+        sort: SortOrder::Selector,
+    };
+    let raw = Converter::format_contract(
+        "T",
+        &items,
+        &docs,
+        OutputFormat::Raw,
+        true,
+        by_selector,
+        &Filter::default(),
+    );
+    let functions: Vec<&str> = raw.lines().filter(|l| l.starts_with("function")).collect();
+    assert_eq!(
+        functions,
+        [
+            "function approve()",
+            "function transfer(address to)",
+            "function balanceOf(address a) view returns (uint256)"
+        ]
+    );
+
+    assert!(SortOrder::parse("bogus").is_err());
+}
+
+#[test]
+fn test_filter_items() {
+    let json = r#"[
+        {"type": "constructor", "inputs": [], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "function", "name": "getReserves", "stateMutability": "view",
+         "inputs": [], "outputs": []},
+        {"type": "function", "name": "getAmountOut", "stateMutability": "pure",
+         "inputs": [], "outputs": []},
+        {"type": "event", "name": "Transfer", "inputs": [], "anonymous": false},
+        {"type": "error", "name": "Bad", "inputs": []}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let names = |filter: &Filter| -> Vec<String> {
+        filter
+            .apply(&items)
+            .iter()
+            .map(|item| item.name.clone().unwrap_or_else(|| item.r#type.clone()))
+            .collect()
+    };
+
+    let mut reads = Filter::default();
+    reads.add_mutability("view,pure").unwrap();
+    assert_eq!(names(&reads), ["getReserves", "getAmountOut"]);
+
+    let mut getters = Filter::default();
+    getters.add_kinds("functions").unwrap();
+    getters.add_names("get*");
+    getters.add_mutability("!pure").unwrap();
+    assert_eq!(names(&getters), ["getReserves"]);
+
+    let mut not_functions = Filter::default();
+    not_functions.add_kinds("!function").unwrap();
+    assert_eq!(names(&not_functions), ["constructor", "Transfer", "Bad"]);
+
+    // transfer(address,uint256) = 0xa9059cbb; Transfer() topic starts 0x406dade3
+    let mut by_hash = Filter::default();
+    by_hash.add_selectors("0xA9059CBB, 406dade3").unwrap();
+    assert_eq!(names(&by_hash), ["transfer", "Transfer"]);
+
+    assert!(Filter::default().add_kinds("struct").is_err());
+    assert!(Filter::default().add_mutability("constant").is_err());
+    assert!(Filter::default().add_selectors("0xzz").is_err());
+}
+
+#[test]
+fn test_structured_output() {
+    let json = r#"[
+        {"type": "function", "name": "swap", "stateMutability": "payable",
+         "inputs": [{"name": "path", "type": "tuple[]", "components": [
+            {"name": "token", "type": "address"}, {"name": "fee", "type": "uint24"}]}],
+         "outputs": [{"name": "out", "type": "uint256"}]},
+        {"type": "event", "name": "Transfer", "anonymous": false,
+         "inputs": [{"name": "from", "type": "address", "indexed": true}]}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let structured = Converter::format_items(
+        "Router",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Structured,
+        false,
+    );
+
+    assert_eq!(
+        structured,
+        concat!(
+            r#"[{"kind":"function","name":"swap","#,
+            r#""signature":"function swap(tuple[] path) payable returns (uint256 out)","#,
+            r#""canonicalSignature":"swap((address,uint24)[])","selector":"0xc12836f4","#,
+            r#""stateMutability":"payable","inputs":[{"name":"path","type":"tuple[]","#,
+            r#""canonicalType":"(address,uint24)[]","components":["#,
+            r#"{"name":"token","type":"address","canonicalType":"address"},"#,
+            r#"{"name":"fee","type":"uint24","canonicalType":"uint24"}]}],"#,
+            r#""outputs":[{"name":"out","type":"uint256","canonicalType":"uint256"}]},"#,
+            r#"{"kind":"event","name":"Transfer","signature":"event Transfer(address indexed from)","#,
+            r#""canonicalSignature":"Transfer(address)","#,
+            r#""topic":"0x2d339b1e3334d3f43e7aa6d5b1fa3ca5e4228b2dd547d1710c726019d98e53fa","#,
+            r#""anonymous":false,"inputs":[{"name":"from","type":"address","#,
+            r#""canonicalType":"address","indexed":true}]}]"#
+        )
+    );
+
+    let grouped = Converter::format_contract(
+        "Router",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Structured,
+        false,
+        Layout {
+            group: true,
+            sort: SortOrder::Source,
+        },
+        &Filter::default(),
+    );
+    assert!(grouped.starts_with(r#"{"payable":[{"kind":"function","name":"swap""#));
+    assert!(grouped.contains(r#""events":[{"kind":"event","name":"Transfer""#));
+
+    let mut streamed = Vec::new();
+    let options = ConvertOptions {
+        format: OutputFormat::Structured,
+        pretty: false,
+        ..ConvertOptions::default()
+    };
+    convert_stream(json.as_bytes(), &mut streamed, &options).unwrap();
+    assert_eq!(
+        String::from_utf8(streamed).unwrap(),
+        format!("{structured}\n")
+    );
+}
+
+#[test]
+fn test_ndjson_output() {
+    let json = r#"[
+        {"type": "function", "name": "name", "stateMutability": "view",
+         "inputs": [], "outputs": [{"name": "", "type": "string"}]},
+        {"type": "error", "name": "Bad", "inputs": []}
+    ]"#;
+    let items = Converter::parse_abi_content(json).unwrap();
+    let lines = Converter::format_items(
+        "Token",
+        &items,
+        &NatSpec::default(),
+        OutputFormat::Ndjson,
+        true,
+    );
+
+    assert_eq!(
+        lines,
+        concat!(
+            r#"{"contract":"Token","kind":"function","name":"name","#,
+            r#""signature":"function name() view returns (string)","#,
+            r#""canonicalSignature":"name()","selector":"0x06fdde03","#,
+            r#""stateMutability":"view","inputs":[],"#,
+            r#""outputs":[{"name":"","type":"string","canonicalType":"string"}]}"#,
+            "\n",
+            r#"{"contract":"Token","kind":"error","name":"Bad","signature":"error Bad()","#,
+            r#""canonicalSignature":"Bad()","selector":"0xe143a034","inputs":[]}"#
+        )
+    );
+
+    let sections = vec![
+        ("Token".to_string(), items.clone()),
+        ("Empty".to_string(), Vec::new()),
+        ("Vault".to_string(), items[1..].to_vec()),
+    ];
+    let combined = Converter::format_sections(&sections, OutputFormat::Ndjson, true);
+    assert_eq!(combined.lines().count(), 3);
+    assert!(combined
+        .lines()
+        .last()
+        .unwrap()
+        .starts_with(r#"{"contract":"Vault","kind":"error""#));
+
+    let mut streamed = Vec::new();
+    let options = ConvertOptions {
+        format: OutputFormat::Ndjson,
+        ..ConvertOptions::default()
+    };
+    convert_stream(json.as_bytes(), &mut streamed, &options).unwrap();
+    let streamed = String::from_utf8(streamed).unwrap();
+    assert_eq!(
+        streamed,
+        format!("{}\n", lines.replace("Token", "Contract"))
+    );
+}
+
+#[test]
+fn test_ndjson_nested_artifacts() {
+    let dir = std::env::temp_dir().join(format!("abi2human-ndjson-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("Token.sol")).unwrap();
+    std::fs::create_dir_all(dir.join("Vault.sol")).unwrap();
+    std::fs::write(
+        dir.join("Token.sol/Token.json"),
+        r#"{"abi": [{"type": "error", "name": "Bad", "inputs": []}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("Vault.sol/Vault.json"),
+        r#"{"abi": [{"type": "event", "name": "Paused", "inputs": [], "anonymous": false}]}"#,
+    )
+    .unwrap();
+
+    let options = ConvertOptions {
+        format: OutputFormat::Ndjson,
+        ..ConvertOptions::default()
+    };
+    let (document, results) = combine_directory(&dir, &dir.join("readable"), &options);
+    let token = dir.join("Token.sol/Token.json").display().to_string();
+    let vault = dir.join("Vault.sol/Vault.json").display().to_string();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let document = document.unwrap();
+    let lines: Vec<&str> = document.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(&format!(
+        r#"{{"file":"{token}","contract":"Token","kind":"error""#
+    )));
+    assert!(lines[1].starts_with(&format!(
+        r#"{{"file":"{vault}","contract":"Vault","kind":"event""#
+    )));
+    assert!(results.iter().all(|r| r.success));
+}
+
+#[test]
+fn test_combine_directory() {
+    let dir = std::env::temp_dir().join(format!("abi2human-combine-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let token = r#"[{"type": "function", "name": "totalSupply", "stateMutability": "view",
+        "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]"#;
+    let vault = r#"[{"type": "function", "name": "deposit", "stateMutability": "payable",
+        "inputs": [], "outputs": []}]"#;
+    std::fs::write(dir.join("Token.json"), token).unwrap();
+    std::fs::write(dir.join("Token.abi.json"), token).unwrap();
+    std::fs::write(dir.join("Vault.json"), vault).unwrap();
+    std::fs::write(dir.join("Broken.json"), "{").unwrap();
+
+    let json = ConvertOptions {
+        pretty: false,
+        combine: true,
+        ..ConvertOptions::default()
+    };
+    let (document, results) = combine_directory(&dir, &dir.join("readable"), &json);
+    let raw = ConvertOptions {
+        format: OutputFormat::Raw,
+        combine: true,
+        ..ConvertOptions::default()
+    };
+    let (raw_document, _) = combine_directory(&dir, &dir.join("readable"), &raw);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        document.unwrap(),
+        concat!(
+            r#"{"Token":["function totalSupply() view returns (uint256)"],"#,
+            r#""Token1":["function totalSupply() view returns (uint256)"],"#,
+            r#""Vault":["function deposit() payable"]}"#
+        )
+    );
+    assert_eq!(
+        raw_document.unwrap(),
+        "// Token\n\
+         function totalSupply() view returns (uint256)\n\
+         // Token1\n\
+         function totalSupply() view returns (uint256)\n\
+         // Vault\n\
+         function deposit() payable"
+    );
+    assert_eq!(results.len(), 4);
+    assert_eq!(results.iter().filter(|r| !r.success).count(), 1);
+}
+
+#[test]
+fn test_combine_foundry_tree() {
+    let dir = std::env::temp_dir().join(format!("abi2human-foundry-{}", std::process::id()));
+    for sub in ["Token.sol", "Vault.sol", "build-info", "cache"] {
+        std::fs::create_dir_all(dir.join(sub)).unwrap();
+    }
+    std::fs::write(
+        dir.join("Token.sol/Token.json"),
+        r#"{"abi": [{"type": "function", "name": "totalSupply", "stateMutability": "view",
+            "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("Vault.sol/Vault.json"),
+        r#"{"abi": [{"type": "function", "name": "deposit", "stateMutability": "payable",
+            "inputs": [], "outputs": []}]}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("build-info/0a1b.json"), r#"{"id": "0a1b"}"#).unwrap();
+    std::fs::write(dir.join("cache/solidity-files-cache.json"), "{}").unwrap();
+
+    let raw = ConvertOptions {
+        format: OutputFormat::Raw,
+        combine: true,
+        ..ConvertOptions::default()
+    };
+    let (document, results) = combine_directory(&dir, &dir.join("readable"), &raw);
+    let empty = dir.join("empty");
+    std::fs::create_dir_all(&empty).unwrap();
+    let (empty_document, empty_results) = combine_directory(&empty, &empty.join("readable"), &raw);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        document.unwrap(),
+        "// Token\n\
+         function totalSupply() view returns (uint256)\n\
+         // Vault\n\
+         function deposit() payable"
+    );
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.success));
+
+    assert!(empty_document.is_none());
+    assert_eq!(empty_results.len(), 1);
+    assert!(!empty_results[0].success);
+}
+
+#[test]
+fn test_combine_directory_twice() {
+    let dir = std::env::temp_dir().join(format!("abi2human-rerun-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("Token.sol")).unwrap();
+    std::fs::write(
+        dir.join("Token.sol/Token.json"),
+        r#"{"abi": [{"type": "function", "name": "totalSupply", "stateMutability": "view",
+            "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}]}"#,
+    )
+    .unwrap();
+
+    let output = dir.join("readable");
+    let mut runs = Vec::new();
+    for format in [OutputFormat::Json, OutputFormat::Html, OutputFormat::Ndjson] {
+        let options = ConvertOptions {
+            format,
+            combine: true,
+            ..ConvertOptions::default()
+        };
+        for _ in 0..2 {
+            runs.push(convert_directory(&dir, &output, &options));
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    for results in runs {
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success, "{:?}", results[0].error);
+        assert!(results[0].input_path.ends_with("Token.sol/Token.json"));
+    }
+}
+
+#[test]
+fn test_hoist_common_items() {
+    let parse =
+        |signatures: &[&str]| Converter::parse_human_readable(&signatures.join("\n")).unwrap();
+    let sections = vec![
+        (
+            "Token".to_string(),
+            parse(&[
+                "function owner() view returns (address)",
+                "function totalSupply() view returns (uint256)",
+                "event Paused(address account)",
+                "constructor()",
+            ]),
+        ),
+        (
+            "Vault".to_string(),
+            parse(&[
+                "function owner() view returns (address)",
+                "function deposit() payable",
+                "event Paused(address account)",
+                "constructor()",
+            ]),
+        ),
+        (
+            "Pool".to_string(),
+            parse(&[
+                "function owner() view returns (address)",
+                // Same name, different parameter name: not identical.
+                "event Paused(address by)",
+            ]),
+        ),
+    ];
+
+    assert_eq!(
+        Converter::format_sections(&hoist_common(&sections).sections(), OutputFormat::Raw, true),
+        "// common 1\n\
+         function owner() view returns (address)\n\
+         // common 2\n\
+         event Paused(address account)\n\
+         // Token + common 1, common 2\n\
+         function totalSupply() view returns (uint256)\n\
+         constructor()\n\
+         // Vault + common 1, common 2\n\
+         function deposit() payable\n\
+         constructor()\n\
+         // Pool + common 1\n\
+         event Paused(address by)"
+    );
+
+    let json = Converter::format_hoisted(
+        &hoist_common(&sections[1..]),
+        OutputFormat::Json,
+        false,
+        Layout::default(),
+    );
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"common":{"common":["function owner() view returns (address)"]},"#,
+            r#""contracts":{"Vault":{"uses":["common"],"#,
+            r#""items":["function deposit() payable","event Paused(address account)","constructor()"]},"#,
+            r#""Pool":{"uses":["common"],"items":["event Paused(address by)"]}}}"#
+        )
+    );
+
+    let unshared = vec![("Token".to_string(), sections[0].1.clone())];
+    let hoisted = hoist_common(&unshared);
+    assert!(hoisted.common.is_empty());
+    assert_eq!(hoisted.contracts[0].name, "Token");
+    assert!(hoisted.contracts[0].uses.is_empty());
+    assert_eq!(hoisted.contracts[0].items.len(), 4);
+}
+
+#[test]
+fn test_detect_standard_interfaces() {
+    let token = Converter::parse_human_readable(
+        "function totalSupply() view returns (uint256)
+         function balanceOf(address owner) view returns (uint256)
+         function transfer(address to, uint256 amount) returns (bool)
+         function transferFrom(address from, address to, uint256 amount) returns (bool)
+         function approve(address spender, uint256 amount) returns (bool)
+         function allowance(address owner, address spender) view returns (uint256)
+         event Transfer(address indexed from, address indexed to, uint256 value)
+         event Approval(address indexed owner, address indexed spender, uint256 value)
+         function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)
+         function nonces(address owner) view returns (uint256)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        summary(&token).unwrap(),
+        "ERC-20, ERC-2612 (partial, missing DOMAIN_SEPARATOR())"
+    );
+    let raw = Converter::format_contract(
+        "Token",
+        &token,
+        &NatSpec::default(),
+        OutputFormat::Raw,
+        true,
+        Layout::default(),
+        &Filter::default(),
+    );
+    assert!(raw.starts_with(
+        "// Whole ABI implements ERC-20, ERC-2612 (partial, missing DOMAIN_SEPARATOR())\n"
+    ));
+
+    // The header still describes the whole ABI under a filter, and is left
+    // out when the filter keeps nothing.
+    let format_filtered = |kinds: &str| {
+        let mut filter = Filter::default();
+        filter.add_kinds(kinds).unwrap();
+        Converter::format_contract(
+            "Token",
+            &token,
+            &NatSpec::default(),
+            OutputFormat::Raw,
+            true,
+            Layout::default(),
+            &filter,
+        )
+    };
+    assert_eq!(
+        format_filtered("events"),
+        "// Whole ABI implements ERC-20, ERC-2612 (partial, missing DOMAIN_SEPARATOR())\n\
+         event Transfer(address indexed from, address indexed to, uint256 value)\n\
+         event Approval(address indexed owner, address indexed spender, uint256 value)"
+    );
+    assert_eq!(format_filtered("errors"), "");
+
+    // ERC-721's Transfer and Approval index the token id, so an NFT is not
+    // reported as a partial ERC-20, and vice versa.
+    let nft = Converter::parse_human_readable(
+        "function balanceOf(address owner) view returns (uint256)
+         function ownerOf(uint256 id) view returns (address)
+         function safeTransferFrom(address from, address to, uint256 id, bytes data)
+         function safeTransferFrom(address from, address to, uint256 id)
+         function transferFrom(address from, address to, uint256 id)
+         function approve(address to, uint256 id)
+         function setApprovalForAll(address operator, bool approved)
+         function getApproved(uint256 id) view returns (address)
+         function isApprovedForAll(address owner, address operator) view returns (bool)
+         event Transfer(address indexed from, address indexed to, uint256 indexed id)
+         event Approval(address indexed owner, address indexed approved, uint256 indexed id)
+         event ApprovalForAll(address indexed owner, address indexed operator, bool approved)
+         function name() view returns (string)
+         function symbol() view returns (string)
+         function supportsInterface(bytes4 id) view returns (bool)",
+    )
+    .unwrap();
+    let detected = detect(&nft);
+    let support = |name: &str| {
+        detected
+            .iter()
+            .find(|(standard, _)| standard.name == name)
+            .map(|(_, support)| support.clone())
+            .unwrap()
+    };
+    assert_eq!(support("ERC-721"), Support::Full);
+    assert_eq!(support("ERC-165"), Support::Full);
+    assert_eq!(support("ERC-20"), Support::Absent);
+    assert_eq!(
+        support("ERC-721 Metadata"),
+        Support::Partial(vec!["tokenURI(uint256)".to_string()])
+    );
+    // Extensions are only reported alongside their base standard.
+    assert_eq!(
+        detect(&token)
+            .iter()
+            .find(|(standard, _)| standard.name == "ERC-721 Enumerable")
+            .map(|(_, support)| support.clone()),
+        Some(Support::Absent)
+    );
+    assert_eq!(summary(&[]), None);
+}
+
+#[test]
+fn test_check_conformance() {
+    let reference = Converter::parse_human_readable(
+        "function balanceOf(address owner) view returns (uint256)
+         function totalSupply() view returns (uint256)
+         function transfer(address to, uint256 amount) returns (bool)
+         function mint(address to, uint256 amount)
+         function burn(uint256 amount)
+         function deposit() payable
+         event Transfer(address indexed from, address indexed to, uint256 value)
+         error Unauthorized(address caller)",
+    )
+    .unwrap();
+    let implementation = Converter::parse_human_readable(
+        "function balanceOf(address owner) pure returns (uint256)
+         function totalSupply() returns (uint256)
+         function transfer(address to, uint256 amount)
+         function mint(address to, uint128 amount)
+         function deposit() view
+         event Transfer(address indexed from, address to, uint256 value)
+         error Unauthorized(address caller)",
+    )
+    .unwrap();
+
+    let issues: Vec<String> = check_conformance(&implementation, &reference)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        issues,
+        [
+            "function totalSupply(): mutability differs, expected view, found nonpayable",
+            "function transfer(address,uint256): returns differs, expected (bool), found ()",
+            "function mint(address,uint256): signature differs, \
+             expected mint(address,uint256), found mint(address,uint128)",
+            "function burn(uint256): missing",
+            "function deposit(): mutability differs, expected payable, found view",
+            "event Transfer(address,address,uint256): indexed differs, \
+             expected (address indexed,address indexed,uint256), \
+             found (address indexed,address,uint256)",
+        ]
+    );
+    assert!(check_conformance(&reference, &reference).is_empty());
+}
+
+#[test]
+fn test_diff_abis() {
+    let old = Converter::parse_human_readable(
+        "function transfer(address to, uint256 amount) returns (bool)
+         function mint(address to, uint256 amount)
+         function burn(uint256 amount)
+         function deposit() payable
+         function price() view returns (uint256)
+         function balance() view returns (uint256)
+         event Transfer(address indexed from, address indexed to, uint256 value)
+         error Expired()",
+    )
+    .unwrap();
+    let new = Converter::parse_human_readable(
+        "function transfer(address recipient, uint256 amount) returns (bool)
+         function mint(address to, uint128 amount)
+         function deposit()
+         function price() pure returns (uint256)
+         function balance() view returns (uint128)
+         function permit()
+         event Transfer(address indexed from, address to, uint256 value)",
+    )
+    .unwrap();
+
+    let changes = diff_abis(&old, &new);
+    let lines: Vec<String> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "~ function transfer(address,uint256): parameter names (to, amount) → (recipient, amount)",
+            "~ function deposit(): mutability payable → nonpayable [breaking]",
+            "~ function price(): mutability view → pure",
+            "~ function balance(): returns (uint256) → (uint128) [breaking]",
+            "~ event Transfer(address,address,uint256): indexed \
+             (address indexed,address indexed,uint256) → (address indexed,address,uint256) [breaking]",
+            "~ function mint(address,uint256): parameters mint(address,uint256) → \
+             mint(address,uint128) [breaking]",
+            "- function burn(uint256) [breaking]",
+            "- error Expired()",
+            "+ function permit()",
+        ]
+    );
+    assert!(diff_abis(&old, &old).is_empty());
+}
+
+#[test]
+fn test_is_command() {
+    let dir = std::env::temp_dir().join(format!("abi2human-commands-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("diff")).unwrap();
+    let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+    let commands = [
+        is_command(&dir, &args(&["check"])),
+        is_command(&dir, &args(&["diff", "old.json", "new.json"])),
+        is_command(&dir, &args(&["diff", "out"])),
+    ];
+    let conversions = [
+        is_command(&dir, &args(&["diff"])),
+        is_command(&dir, &args(&["./diff", "out"])),
+        is_command(&dir, &args(&["Token.json"])),
+        is_command(&dir, &args(&[])),
+    ];
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(commands, [true; 3]);
+    assert_eq!(conversions, [false; 4]);
+}
+
+#[test]
+fn test_split_revision_range() {
+    let range = |from: &str, to: &str| Ok((from.to_string(), to.to_string()));
+    assert_eq!(split_range("HEAD~1..HEAD"), range("HEAD~1", "HEAD"));
+    assert_eq!(split_range("v1.0..main"), range("v1.0", "main"));
+    assert_eq!(split_range("v1.0.."), range("v1.0", "HEAD"));
+    assert_eq!(split_range("..main"), range("HEAD", "main"));
+    assert!(split_range("HEAD").is_err());
+    assert!(split_range("main...feature").is_err());
+    assert!(split_range("--output=x..HEAD").is_err());
+    assert!(split_range("HEAD..-p").is_err());
+}
+
+#[test]
+fn test_diff_revisions() {
+    let dir = std::env::temp_dir().join(format!("abi2human-git-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("out/Token.sol")).unwrap();
+    std::fs::create_dir_all(dir.join("out/build-info")).unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(&dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    };
+    let write = |file: &str, content: &str| std::fs::write(dir.join(file), content).unwrap();
+    let commit = |message: &str| {
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", message]);
+    };
+
+    git(&["init", "-q"]);
+    write(
+        "out/Token.sol/Token.json",
+        r#"{"abi": [
+            {"type": "function", "name": "price", "stateMutability": "view",
+             "inputs": [], "outputs": [{"name": "", "type": "uint256"}]},
+            {"type": "function", "name": "burn", "stateMutability": "nonpayable",
+             "inputs": [{"name": "amount", "type": "uint256"}], "outputs": []}
+        ]}"#,
+    );
+    write("out/build-info/0a1b.json", r#"{"id": "0a1b"}"#);
+    commit("first");
+
+    write(
+        "out/Token.sol/Token.json",
+        r#"{"abi": [
+            {"type": "function", "name": "price", "stateMutability": "nonpayable",
+             "inputs": [], "outputs": [{"name": "", "type": "uint256"}]}
+        ]}"#,
+    );
+    write("out/build-info/0a1b.json", r#"{"id": "0a1b", "input": {}}"#);
+    commit("second");
+
+    write("out/Token.sol/Token.json", r#"{"abi": ["#);
+    commit("third");
+
+    let changes = diff_revisions(&dir, "HEAD~2..HEAD~1", "out", ParseMode::default());
+    let broken = diff_revisions(&dir, "HEAD~1..HEAD", "out", ParseMode::default());
+    let unchanged = diff_revisions(&dir, "HEAD~1..HEAD~1", "out", ParseMode::default());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let changes = changes.unwrap();
+    assert_eq!(changes.len(), 1);
+    let (file, changes) = &changes[0];
+    assert_eq!(file, "out/Token.sol/Token.json");
+    let lines: Vec<String> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        [
+            "~ function price(): mutability view → nonpayable [breaking]",
+            "- function burn(uint256) [breaking]",
+        ]
+    );
+
+    assert!(broken
+        .unwrap_err()
+        .starts_with("Failed to parse out/Token.sol/Token.json at HEAD:"));
+    assert!(unchanged.unwrap().is_empty());
+}
@@ -0,0 +1,174 @@
+use crate::abi::AbiItem;
+use crate::json_parser::{JsonParser, ParseMode};
+use std::path::Path;
+
+const SCRIPT_EXTENSIONS: &[&str] = &["ts", "mts", "cts", "js", "mjs", "cjs"];
+
+pub fn is_script_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+}
+
+/// Finds every `export const <name> = [...]` in a TypeScript or JavaScript
+/// module and parses the array literal as a JSON5-style ABI. Exports whose
+/// array contains no ABI items (address lists, chain ids) or is not a
+/// literal at all (`[mainnet, sepolia]`) are skipped; a parse failure is
+/// only reported when no export yields an ABI.
+pub fn extract_exported_abis(source: &str) -> Result<Vec<(String, Vec<AbiItem>)>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut exports = Vec::new();
+    let mut failure = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if let Some(end) = skip_string_or_comment(&chars, i) {
+            i = end;
+            continue;
+        }
+
+        if !starts_word(&chars, i, "export") {
+            i += 1;
+            continue;
+        }
+
+        let mut pos = skip_trivia(&chars, i + "export".len());
+        let declaration = ["const", "let", "var"]
+            .into_iter()
+            .find(|kw| starts_word(&chars, pos, kw));
+        let Some(keyword) = declaration else {
+            i = pos;
+            continue;
+        };
+        pos = skip_trivia(&chars, pos + keyword.len());
+
+        let name_start = pos;
+        while pos < chars.len() && is_ident_char(chars[pos]) {
+            pos += 1;
+        }
+        let name: String = chars[name_start..pos].iter().collect();
+        if name.is_empty() {
+            i = pos;
+            continue;
+        }
+
+        // Skip an optional type annotation such as `: Abi`.
+        while pos < chars.len() && chars[pos] != '=' && chars[pos] != ';' {
+            pos += 1;
+        }
+        if chars.get(pos) != Some(&'=') {
+            i = pos;
+            continue;
+        }
+        pos = skip_trivia(&chars, pos + 1);
+
+        if chars.get(pos) != Some(&'[') {
+            i = pos;
+            continue;
+        }
+
+        let end = find_closing_bracket(&chars, pos)
+            .ok_or_else(|| format!("Unterminated array literal in export '{name}'"))?;
+        let literal: String = chars[pos..end].iter().collect();
+
+        match JsonParser::with_mode(&literal, ParseMode::Json5).parse_abi() {
+            Ok(items) if !items.is_empty() => exports.push((name, items)),
+            Ok(_) => {}
+            Err(e) => {
+                failure.get_or_insert_with(|| format!("Failed to parse export '{name}': {e}"));
+            }
+        }
+
+        i = end;
+    }
+
+    match failure {
+        Some(error) if exports.is_empty() => Err(error),
+        _ => Ok(exports),
+    }
+}
+
+/// Returns the index just past the `]` matching the `[` at `start`.
+fn find_closing_bracket(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = start;
+
+    while i < chars.len() {
+        if let Some(end) = skip_string_or_comment(chars, i) {
+            i = end;
+            continue;
+        }
+        match chars[i] {
+            '[' | '{' | '(' => depth += 1,
+            ']' | '}' | ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// If a string literal or comment starts at `i`, returns the index just past
+/// its end.
+fn skip_string_or_comment(chars: &[char], i: usize) -> Option<usize> {
+    match (chars[i], chars.get(i + 1)) {
+        ('/', Some('/')) => {
+            let mut j = i + 2;
+            while j < chars.len() && chars[j] != '\n' {
+                j += 1;
+            }
+            Some(j)
+        }
+        ('/', Some('*')) => {
+            let mut j = i + 2;
+            while j < chars.len() && !(chars[j] == '*' && chars.get(j + 1) == Some(&'/')) {
+                j += 1;
+            }
+            Some((j + 2).min(chars.len()))
+        }
+        (quote @ ('"' | '\'' | '`'), _) => {
+            let mut j = i + 1;
+            while j < chars.len() && chars[j] != quote {
+                if chars[j] == '\\' {
+                    j += 1;
+                }
+                j += 1;
+            }
+            Some((j + 1).min(chars.len()))
+        }
+        _ => None,
+    }
+}
+
+fn skip_trivia(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            i += 1;
+        } else if chars[i] == '/' && matches!(chars.get(i + 1), Some('/') | Some('*')) {
+            i = skip_string_or_comment(chars, i).unwrap_or(chars.len());
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+fn starts_word(chars: &[char], i: usize, word: &str) -> bool {
+    let len = word.chars().count();
+    if i + len > chars.len() || !chars[i..i + len].iter().copied().eq(word.chars()) {
+        return false;
+    }
+    let before_ok = i == 0 || !is_ident_char(chars[i - 1]);
+    let after_ok = chars.get(i + len).is_none_or(|&c| !is_ident_char(c));
+    before_ok && after_ok
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
@@ -0,0 +1,82 @@
+use crate::abi::AbiItem;
+use crate::canonical::{kind_rank, normalize_item};
+use crate::json_writer::JsonValue;
+
+/// Renders named ABIs as a TypeScript module with one
+/// `export const fooAbi = [...] as const` per ABI, the form viem and wagmi
+/// infer types from. Items keep their ABI order but are normalised so
+/// every function carries `stateMutability`.
+pub fn render_module(contracts: &[(String, Vec<AbiItem>)], pretty: bool) -> String {
+    let exports: Vec<String> = contracts
+        .iter()
+        .map(|(name, abi)| {
+            let ident = abi_ident(name);
+            let items = abi
+                .iter()
+                .filter(|item| kind_rank(&item.r#type).is_some())
+                .map(|item| normalize_item(item).to_json())
+                .collect();
+            format!(
+                "export const {ident} = {} as const;\n\nexport type {} = typeof {ident};",
+                JsonValue::Array(items).render(pretty),
+                upper_first(&ident)
+            )
+        })
+        .collect();
+
+    format!(
+        "// Generated by abi2human. Do not edit.\n\n{}",
+        exports.join("\n\n")
+    )
+}
+
+/// `Token` becomes `tokenAbi`, `ERC20Permit` becomes `erc20PermitAbi` and
+/// `my-token` becomes `myTokenAbi`.
+fn abi_ident(name: &str) -> String {
+    let words: Vec<&str> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut ident: String = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            if i == 0 {
+                lower_first(word)
+            } else {
+                upper_first(word)
+            }
+        })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident.push_str("Abi");
+    ident
+}
+
+/// Lowercases a leading run of capitals, leaving the last one when it
+/// starts the next word: `USDCToken` becomes `usdcToken`.
+fn lower_first(word: &str) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let run = chars.iter().take_while(|c| c.is_ascii_uppercase()).count();
+    let cut = if run > 1 && chars.get(run).is_some_and(|c| c.is_ascii_lowercase()) {
+        run - 1
+    } else {
+        run.max(1)
+    };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i < cut { c.to_ascii_lowercase() } else { *c })
+        .collect()
+}
+
+fn upper_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}